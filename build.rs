@@ -0,0 +1,152 @@
+/// Generates code from the solutions in `src/bin` and the example answers in `data/<year>/examples`:
+///  1. a module list that the runner binary includes as its solution registry with the `registry` feature. This allows it to run solutions in-process.
+///  2. one test per example file and part listed in `data/<year>/examples/<day>.answers`, included by the `example_tests!` macro.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+#[path = "src/template/config_file.rs"]
+mod config_file;
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let bin_dir = manifest_dir.join("src").join("bin");
+    let data_dir = manifest_dir.join(find_data_dir(&manifest_dir));
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let puzzles = find_puzzles(&bin_dir);
+
+    fs::write(
+        out_dir.join("solutions.rs"),
+        generate_registry(&bin_dir, &puzzles),
+    )
+    .unwrap();
+
+    let tests_dir = out_dir.join("examples");
+    fs::create_dir_all(&tests_dir).unwrap();
+
+    for (year, day) in &puzzles {
+        let examples_dir = data_dir.join(year).join("examples");
+        println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+        let tests = generate_example_tests(&examples_dir, year, day);
+        // the `example_tests!` macro refers to the tests by the name of the solution binary.
+        fs::write(tests_dir.join(format!("{year}-{day}.rs")), tests).unwrap();
+    }
+}
+
+/// The data directory is configured by `data_dir` in `aoc.toml` or `AOC_DATA_DIR`, see `src/template/config.rs`.
+/// The solutions validate the rest of the configuration, an invalid file falls back to the default directory here.
+fn find_data_dir(manifest_dir: &Path) -> String {
+    let config_path = manifest_dir.join(config_file::CONFIG_FILE_NAME);
+    println!("cargo:rerun-if-changed={}", config_path.display());
+    println!("cargo:rerun-if-env-changed=AOC_DATA_DIR");
+
+    if let Ok(data_dir) = env::var("AOC_DATA_DIR") {
+        return data_dir;
+    }
+
+    let config = fs::read_to_string(config_path).unwrap_or_default();
+    config_file::read_settings(&config)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|(key, _)| key == "data_dir")
+        .and_then(|(_, value)| value.as_str().map(String::from))
+        .unwrap_or_else(|| "data".into())
+}
+
+/// Find the year and padded day number of all solutions in `src/bin`, named like `2023-01.rs`.
+fn find_puzzles(bin_dir: &Path) -> Vec<(String, String)> {
+    let mut puzzles: Vec<(String, String)> = fs::read_dir(bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let (year, day) = name.strip_suffix(".rs")?.split_once('-')?;
+            let is_year = year.len() == 4 && year.chars().all(|c| c.is_ascii_digit());
+            let is_day = day.len() == 2 && day.chars().all(|c| c.is_ascii_digit());
+            (is_year && is_day).then(|| (year.to_string(), day.to_string()))
+        })
+        .collect();
+
+    puzzles.sort_unstable();
+    puzzles
+}
+
+fn generate_registry(bin_dir: &Path, puzzles: &[(String, String)]) -> String {
+    let mut lines: Vec<String> = vec![];
+
+    for (year, day) in puzzles {
+        let path = bin_dir.join(format!("{year}-{day}.rs"));
+        lines.push(format!("#[path = {:?}]", path.display().to_string()));
+        lines.push(format!("pub mod year{year}_day{day};"));
+    }
+
+    lines.push(String::new());
+    lines.push("pub static SOLUTIONS: &[(Year, &dyn Solution)] = &[".into());
+    for (year, day) in puzzles {
+        lines.push(format!(
+            "    (advent_of_code::year!({year}), &year{year}_day{day}::Solver),"
+        ));
+    }
+    lines.push("];".into());
+
+    lines.join("\n")
+}
+
+/// Each non-empty line of an answers file has the format `<example file> <part> <expected answer>`.
+/// Lines starting with `#` are comments.
+fn generate_example_tests(examples_dir: &Path, year: &str, day: &str) -> String {
+    let answers_path = examples_dir.join(format!("{day}.answers"));
+
+    let Ok(answers) = fs::read_to_string(&answers_path) else {
+        return String::new();
+    };
+
+    let mut lines: Vec<String> = vec![];
+
+    for (index, line) in answers.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.splitn(3, char::is_whitespace);
+        let file = fields.next().unwrap_or_default();
+        let part = fields.next().unwrap_or_default();
+        let expected = fields.next().unwrap_or_default().trim();
+
+        let part_name = match part {
+            "1" => "part_one",
+            "2" => "part_two",
+            _ => "",
+        };
+
+        if file.is_empty() || part_name.is_empty() || expected.is_empty() {
+            let message = format!(
+                "{}:{}: expected `<example file> <part> <expected answer>`, with part 1 or 2.",
+                answers_path.display(),
+                index + 1
+            );
+            lines.push(format!("compile_error!({message:?});"));
+            continue;
+        }
+
+        let file_name: String = file
+            .trim_end_matches(".txt")
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        lines.push("#[test]".into());
+        lines.push(format!("fn example_{file_name}_{part_name}() {{"));
+        lines.push(format!(
+            "    advent_of_code::template::check_example(&super::Solver, advent_of_code::year!({year}), {file:?}, {part}, {expected:?});"
+        ));
+        lines.push("}".into());
+    }
+
+    lines.join("\n")
+}
@@ -57,21 +57,3 @@ fn find_number(text: &str) -> u32 {
     }
     first_digit.unwrap() * 10 + second_digit.unwrap()
 }
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result.unwrap(), 142);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part("examples", DAY, 2));
-        assert_eq!(result.unwrap(), 281);
-    }
-}
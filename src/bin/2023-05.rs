@@ -4,37 +4,40 @@ use core::ops::Range;
 use std::cmp::{max, min};
 use regex::Regex;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse = parse_input);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let re = Regex::new(r"seeds: ((?:\d+| )+)").unwrap();
-    let captures = re.captures(input).unwrap();
-    let seeds: Vec<u64> = captures[1].split_whitespace().map(|num| num.parse().unwrap()).collect();
-
-    let maps = from_input(input);
-
-    let min = map(&maps, seeds);
+pub fn part_one(almanac: &Almanac) -> Option<u32> {
+    let min = map(&almanac.maps, almanac.seeds.clone());
 
     Some(min as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let re = Regex::new(r"seeds: ((?:\d+| )+)").unwrap();
-    let captures = re.captures(input).unwrap();
-    let seed_input: Vec<u64> = captures[1].split_whitespace().map(|num| num.parse().unwrap()).collect();
-    
+pub fn part_two(almanac: &Almanac) -> Option<u32> {
+    let seed_input = &almanac.seeds;
+
     let mut seeds:  Vec<Range<u64>> = Vec::new();
     for i in 0..seed_input.len() / 2 {
         seeds.push(seed_input[2*i]..seed_input[2*i]+seed_input[2*i+1]);
     }
-    
-    let maps = from_input(input);
 
-    let min = map_range(&maps, seeds);
+    let min = map_range(&almanac.maps, seeds);
 
     Some(min as u32)
 }
 
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<Map>,
+}
+
+fn parse_input(input: &str) -> Almanac {
+    let re = Regex::new(r"seeds: ((?:\d+| )+)").unwrap();
+    let captures = re.captures(input).unwrap();
+    let seeds: Vec<u64> = captures[1].split_whitespace().map(|num| num.parse().unwrap()).collect();
+
+    Almanac { seeds, maps: from_input(input) }
+}
+
 fn from_input(input: &str) -> Vec<Map> {
     let seed_to_soil = Map{ mappings: get_mapping(input, "seed-to-soil map") };
     let soil_to_fertilizer = Map{ mappings: get_mapping(input, "soil-to-fertilizer map") };
@@ -151,21 +154,3 @@ impl Mapping{
         }
     }
 }
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
-    }
-}
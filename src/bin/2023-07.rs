@@ -20,10 +20,8 @@ pub fn part_one(input: &str) -> Option<u32> {
         .collect();
 
     let mut winnings = 0;
-    let mut i = 1;
-    for hand in hands.iter().sorted() {
+    for (i, hand) in (1..).zip(hands.iter().sorted()) {
         winnings += hand.bid * i;
-        i += 1;
     }
 
     Some(winnings)
@@ -43,10 +41,8 @@ pub fn part_two(input: &str) -> Option<u32> {
         .collect();
 
     let mut winnings = 0;
-    let mut i = 1;
-    for hand in hands.iter().sorted() {
+    for (i, hand) in (1..).zip(hands.iter().sorted()) {
         winnings += hand.bid * i;
-        i += 1;
     }
 
     Some(winnings)
@@ -177,20 +173,3 @@ impl PartialOrd for Hand {
         Some(self.cmp(other))
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6440));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5905));
-    }
-}
@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use memoize::memoize;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, reset = memoized_flush_replace);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let records = input.trim().lines().map(|line| {
@@ -85,26 +85,3 @@ fn replace(row: String, numbers: Vec<usize>, last_char: char) -> u64 {
 
     panic!("Should not reach")
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
-    }
-
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(&advent_of_code::template::read_file_part("examples", DAY, 1));
-        assert_eq!(result, Some(7541));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(525152));
-    }
-}
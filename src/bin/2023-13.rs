@@ -85,20 +85,3 @@ fn find_vertical_line(pattern: &str, allowed_defects: u8) -> Option<usize> {
     let transposed = transpose_pattern(pattern);
     find_horizontal_line(transposed.as_str(), allowed_defects)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(405));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(400));
-    }
-}
@@ -1,12 +1,10 @@
 use itertools::{enumerate, Itertools};
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
 use std::collections::HashMap;
 
-advent_of_code::solution!(14);
-
-pub fn part_one(input: &str) -> Option<u32> {
-    let field = parse_input(input);
+advent_of_code::solution!(14, parse = parse_input);
 
+pub fn part_one(field: &Field) -> Option<u32> {
     let rocks = tilt(&field.rocks, Direction::Up, field.width, field.height);
 
     let load = compute_load(rocks, field.height);
@@ -14,10 +12,8 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(load as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let field = parse_input(input);
-
-    let cycle = find_cycle(&field);
+pub fn part_two(field: &Field) -> Option<u32> {
+    let cycle = find_cycle(field);
 
     let remainder = (1_000_000_000 - (cycle.start + cycle.length)) % (cycle.length);
     let mut rocks = cycle.rocks;
@@ -52,7 +48,7 @@ fn parse_input(input: &str) -> Field {
 }
 
 #[derive(Debug, Hash, Eq, PartialEq)]
-struct Field {
+pub struct Field {
     width: usize,
     height: usize,
     rocks: Vec<Rock>,
@@ -64,10 +60,10 @@ fn tilt(rocks: &[Rock], direction: Direction, width: usize, height: usize) -> Ve
 
     let mut rocks = rocks.to_vec();
     match direction {
-        Direction::Up => rocks.sort_by(|a, b| a.y.cmp(&b.y)),
-        Direction::Down => rocks.sort_by(|a, b| b.y.cmp(&a.y)),
-        Direction::Left => rocks.sort_by(|a, b| a.x.cmp(&b.x)),
-        Direction::Right => rocks.sort_by(|a, b| b.x.cmp(&a.x)),
+        Direction::Up => rocks.sort_by_key(|rock| rock.y),
+        Direction::Down => rocks.sort_by_key(|rock| Reverse(rock.y)),
+        Direction::Left => rocks.sort_by_key(|rock| rock.x),
+        Direction::Right => rocks.sort_by_key(|rock| Reverse(rock.x)),
     }
 
     for rock in rocks {
@@ -191,20 +187,3 @@ impl PartialOrd for Rock {
 
 #[derive(Debug, Hash, Eq, PartialEq, Copy, Clone)]
 enum Direction { Up, Down, Left, Right }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(136));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(64));
-    }
-}
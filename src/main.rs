@@ -1,14 +1,29 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, inputs, read, scaffold, solve, status, time, verify, watch,
+};
+use advent_of_code::template::{config, Puzzle};
 use args::{parse, AppArguments};
 
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
+/// The solutions in `src/bin`, which the runner registers to run them in-process, see `build.rs`.
+/// Their binaries run their own tests, so they are not compiled into the tests of the runner.
+#[cfg(all(feature = "registry", not(test)))]
+#[allow(dead_code)]
+mod solutions {
+    use advent_of_code::template::{Solution, Year};
+
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::{time, Execution, Profile};
+    use advent_of_code::template::config::Config;
+    use advent_of_code::template::{Day, InputSource, Year};
+    use std::ffi::OsString;
+    use std::time::Duration;
+    use std::{env, process};
 
     pub enum AppArguments {
         Download {
@@ -16,6 +31,7 @@ mod args {
         },
         Read {
             day: Day,
+            part: Option<u8>,
         },
         Scaffold {
             day: Day,
@@ -26,52 +42,147 @@ mod args {
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            force: bool,
+            input: InputSource,
         },
         All {
-            release: bool,
+            days: Vec<Day>,
+            profile: Profile,
+            execution: Execution,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            execution: Execution,
+            name: Option<String>,
+            compare: Option<time::Compare>,
+        },
+        Verify {
+            days: Vec<Day>,
+            record: bool,
+        },
+        Status,
+        Inputs {
+            day: Day,
+            time: bool,
+            record: bool,
+        },
+        Watch {
+            day: Day,
+            test: bool,
+            solve: bool,
+            release: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    /// Parses the command and the year given by `--year`, if any.
+    pub fn parse(
+        config: &Config,
+    ) -> Result<(Option<Year>, AppArguments), Box<dyn std::error::Error>> {
+        let mut raw_args: Vec<OsString> = env::args_os().skip(1).collect();
+        let mut example = take_example(&mut raw_args)?;
+        let mut args = pico_args::Arguments::from_vec(raw_args);
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+        let subcommand = args.subcommand()?;
+        let year = args.opt_value_from_str("--year")?;
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let profile = Profile::new(args.contains("--release"), args.contains("--dhat"));
+                let execution = parse_execution(&mut args, config)?;
+
+                let mut days = vec![];
+                while let Some(day) = args.opt_free_from_str()? {
+                    days.push(day);
+                }
+
+                AppArguments::All {
+                    days,
+                    profile,
+                    execution,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                // timed runs stay sequential unless `--jobs` is passed, so days do not slow each other down.
+                let execution = parse_execution(&mut args, config)?;
+                let name = args.opt_value_from_str("--name")?;
+
+                let baseline: Option<String> = args.opt_value_from_str("--baseline")?;
+                let threshold = args.opt_value_from_str("--threshold")?.unwrap_or(10.0);
+                let compare =
+                    (args.contains("--compare") || baseline.is_some()).then_some(time::Compare {
+                        baseline,
+                        threshold,
+                    });
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    execution,
+                    name,
+                    compare,
                 }
             }
+            Some("verify") => {
+                let record = args.contains("--record");
+
+                let mut days = vec![];
+                while let Some(day) = args.opt_free_from_str()? {
+                    days.push(day);
+                }
+
+                AppArguments::Verify { days, record }
+            }
+            Some("status") => AppArguments::Status,
+            Some("inputs") => {
+                let time = args.contains("--time");
+                let record = args.contains("--record");
+
+                AppArguments::Inputs {
+                    day: args.free_from_str()?,
+                    time,
+                    record,
+                }
+            }
+            Some("watch") => AppArguments::Watch {
+                day: args.free_from_str()?,
+                test: args.contains("--test"),
+                solve: args.contains("--solve"),
+                release: args.contains("--release"),
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                part: args.opt_value_from_str("--part")?,
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let force = args.contains("--force");
+                let dhat = args.contains("--dhat");
+                let input: Option<String> = args.opt_value_from_str("--input")?;
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    release,
+                    dhat,
+                    submit,
+                    force,
+                    input: InputSource::new(input, example.take())?,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -84,55 +195,155 @@ mod args {
             }
         };
 
-        let remaining = args.finish();
+        let mut remaining = args.finish();
+        if example.is_some() {
+            remaining.push("--example".into());
+        }
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((year, app_args))
+    }
+
+    /// Take `--example [k]` out of the arguments, as pico-args cannot read a value that may be left out.
+    /// A number right after `--example` is the example, the day has to come before it, e.g. `cargo solve 1 --example 2`.
+    fn take_example(args: &mut Vec<OsString>) -> Result<Option<Option<u8>>, String> {
+        let strings: Vec<String> = args
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+
+        let Some((range, example)) = InputSource::find_example(&strings)? else {
+            return Ok(None);
+        };
+
+        args.drain(range);
+        Ok(Some(example))
+    }
+
+    fn parse_execution(
+        args: &mut pico_args::Arguments,
+        config: &Config,
+    ) -> Result<Execution, Box<dyn std::error::Error>> {
+        let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+        if jobs == 0 {
+            return Err("`--jobs` must be at least 1".into());
+        }
+
+        // `--timeout 0` disables a timeout from the configuration.
+        let timeout = match args.opt_value_from_str::<_, u64>("--timeout")? {
+            Some(0) => None,
+            Some(secs) => Some(Duration::from_secs(secs)),
+            None => config.run.timeout,
+        };
+
+        Ok(Execution {
+            is_isolated: args.contains("--isolated"),
+            jobs,
+            timeout,
+        })
     }
 }
 
 fn main() {
-    match parse() {
+    #[cfg(all(feature = "registry", not(test)))]
+    advent_of_code::template::registry::register(solutions::SOLUTIONS);
+
+    let config = match config::init() {
+        Ok(config) => config,
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
-                if download {
-                    download::handle(day);
+    };
+
+    match parse(config) {
+        Err(err) => {
+            eprintln!("Error: {err}");
+            std::process::exit(1);
+        }
+        Ok((year, args)) => {
+            // the year defaults to the configured year, and is only required by commands that use it.
+            let year = || match year.or(config.year) {
+                Some(year) => year,
+                None => {
+                    eprintln!("Error: no `--year` given and no year set in aoc.toml or AOC_YEAR");
+                    std::process::exit(1);
                 }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
+            };
+
+            match args {
+                AppArguments::All {
+                    days,
+                    profile,
+                    execution,
+                } => all::handle(year(), &days, profile, execution),
+                AppArguments::Time {
+                    day,
+                    all,
+                    store,
+                    execution,
+                    name,
+                    compare,
+                } => time::handle(year(), day, all, store, execution, name, compare),
+                AppArguments::Verify { days, record } => verify::handle(year(), &days, record),
+                AppArguments::Status => status::handle(year()),
+                AppArguments::Inputs { day, time, record } => {
+                    inputs::handle(Puzzle::new(year(), day), time, record);
+                }
+                AppArguments::Watch {
+                    day,
+                    test,
+                    solve,
+                    release,
+                } => {
+                    // without `--test` or `--solve`, both are run.
+                    let (test, solve) = if test || solve {
+                        (test, solve)
+                    } else {
+                        (true, true)
+                    };
+                    watch::handle(Puzzle::new(year(), day), test, solve, release);
+                }
+                AppArguments::Download { day } => download::handle(Puzzle::new(year(), day)),
+                AppArguments::Read { day, part } => read::handle(Puzzle::new(year(), day), part),
+                AppArguments::Scaffold { day, download } => {
+                    scaffold::handle(Puzzle::new(year(), day), download);
+                }
+                AppArguments::Solve {
+                    day,
+                    release,
+                    dhat,
+                    submit,
+                    force,
+                    input,
+                } => solve::handle(
+                    Puzzle::new(year(), day),
+                    release,
+                    dhat,
+                    submit,
+                    force,
+                    &input,
+                ),
+                #[cfg(feature = "today")]
+                AppArguments::Today => {
+                    // the puzzle released today belongs to the current year, regardless of `--year`.
+                    match Puzzle::today() {
+                        Some(puzzle) => {
+                            scaffold::handle(puzzle, true);
+                            read::handle(puzzle, None)
+                        }
+                        None => {
+                            eprintln!(
+                                "`today` command can only be run between the 1st and \
                             the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+                            );
+                            process::exit(1)
+                        }
+                    };
+                }
             }
-        },
+        }
     };
 }
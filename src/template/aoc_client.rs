@@ -0,0 +1,377 @@
+/// Built-in client for the Advent of Code website.
+/// Authenticates with the session cookie from `AOC_SESSION` or the session file also used by aoc-cli.
+/// The base URL can be changed with `AOC_BASE_URL`, e.g. to point at a local server in tests.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::config::ConfigError;
+use crate::template::{puzzle, Puzzle};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+/// Selects how the template talks to the Advent of Code website, see the `backend` setting of the [configuration](crate::template::config).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// The built-in client.
+    Native,
+    /// The external `aoc` command-line, see [`aoc_cli`](crate::template::aoc_cli).
+    AocCli,
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    Status(u16, String),
+    Transport(String),
+    IO(io::Error),
+    Config(ConfigError),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or store it in ~/.adventofcode.session."
+            ),
+            ClientError::Status(status, body) => {
+                write!(f, "server responded with status {status}: {}", body.trim())
+            }
+            ClientError::Transport(e) => write!(f, "request failed: {e}"),
+            ClientError::IO(e) => write!(f, "{e}"),
+            ClientError::Config(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::IO(e)
+    }
+}
+
+impl From<ConfigError> for ClientError {
+    fn from(e: ConfigError) -> Self {
+        ClientError::Config(e)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                ClientError::Status(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(e) => ClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// Hint given by the server for a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect(Option<Hint>),
+    /// An answer was submitted too recently, with the time left to wait if known.
+    TooRecent(Option<Duration>),
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+/// The response to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub verdict: Verdict,
+    pub message: String,
+}
+
+impl Submission {
+    fn from_html(html: &str) -> Self {
+        let message = puzzle::article_text(html).unwrap_or_default();
+
+        let verdict = if message.contains("That's the right answer") {
+            Verdict::Correct
+        } else if message.contains("That's not the right answer") {
+            let hint = if message.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if message.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            Verdict::Incorrect(hint)
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::TooRecent(parse_wait(&message))
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        };
+
+        Submission { verdict, message }
+    }
+}
+
+/// Parse the time left to wait from a message like `You have 1m 30s left to wait.`
+fn parse_wait(message: &str) -> Option<Duration> {
+    let end = message.find(" left to wait")?;
+    let start = message[..end].rfind("You have ")? + "You have ".len();
+
+    message[start..end]
+        .split_whitespace()
+        .try_fold(0, |seconds, part| {
+            let (value, factor) = match part.strip_suffix('m') {
+                Some(minutes) => (minutes, 60),
+                None => (part.strip_suffix('s')?, 1),
+            };
+            Some(seconds + value.parse::<u64>().ok()? * factor)
+        })
+        .map(Duration::from_secs)
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// Configure a client from the environment.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = read_session().ok_or(ClientError::MissingSession)?;
+
+        Ok(Client::new(base_url, session))
+    }
+
+    pub fn new(base_url: String, session: String) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    fn day_url(&self, puzzle: Puzzle) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year.into_inner(),
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Fetch the input of `puzzle`.
+    pub fn input(&self, puzzle: Puzzle) -> Result<String, ClientError> {
+        let response = self
+            .agent
+            .get(&format!("{}/input", self.day_url(puzzle)))
+            .set("Cookie", &self.cookie())
+            .call()?;
+
+        Ok(response.into_string()?)
+    }
+
+    /// Fetch the description of `puzzle` as markdown.
+    pub fn puzzle(&self, puzzle: Puzzle) -> Result<String, ClientError> {
+        let response = self
+            .agent
+            .get(&self.day_url(puzzle))
+            .set("Cookie", &self.cookie())
+            .call()?;
+
+        Ok(puzzle::from_html(&response.into_string()?))
+    }
+
+    /// Submit an answer for one part of `puzzle`.
+    pub fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u8,
+        answer: &str,
+    ) -> Result<Submission, ClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(puzzle)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        Ok(Submission::from_html(&response.into_string()?))
+    }
+}
+
+/// Read the session cookie from `AOC_SESSION`, or the file named by `AOC_SESSION_FILE`, which defaults to `~/.adventofcode.session`.
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session.trim().to_string());
+    }
+
+    let path = match env::var("AOC_SESSION_FILE") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => {
+            let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+            PathBuf::from(home).join(".adventofcode.session")
+        }
+    };
+
+    let session = fs::read_to_string(path).ok()?.trim().to_string();
+    (!session.is_empty()).then_some(session)
+}
+
+/// Write a file, creating its directory if it does not exist yet.
+fn write_file(path: &str, contents: &str) -> Result<(), io::Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Download input and description of `puzzle` to the data directory.
+pub fn download(puzzle: Puzzle) -> Result<(), ClientError> {
+    let client = Client::from_env()?;
+
+    let input_path = puzzle.input_path()?;
+    let puzzle_path = puzzle.puzzle_path()?;
+
+    write_file(&input_path, &client.input(puzzle)?)?;
+    write_file(&puzzle_path, &client.puzzle(puzzle)?)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Fetch the description of `puzzle` and store it in the data directory, e.g. to add part two once it is unlocked.
+pub fn refresh_puzzle(puzzle: Puzzle) -> Result<(), ClientError> {
+    let client = Client::from_env()?;
+    let puzzle_path = puzzle.puzzle_path()?;
+
+    write_file(&puzzle_path, &client.puzzle(puzzle)?)?;
+
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Submit an answer and print the server's response.
+pub fn submit(puzzle: Puzzle, part: u8, answer: &str) -> Result<Submission, ClientError> {
+    let client = Client::from_env()?;
+    let submission = client.submit(puzzle, part, answer)?;
+
+    println!("{}", submission.message);
+    Ok(submission)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_wait, Client, Hint, Submission, Verdict};
+    use crate::template::Puzzle;
+    use crate::{day, year};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Serve a single request with `body`, returning the request line, headers and body that were received.
+    fn serve_once(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                request.push_str(&line);
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (base_url, server) = serve_once("1abc2\n");
+        let client = Client::new(base_url, "secret".into());
+        let puzzle = Puzzle::new(year!(2023), day!(1));
+
+        assert_eq!(client.input(puzzle).unwrap(), "1abc2\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input "));
+        assert!(request.contains("session=secret"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve_once(
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+        );
+        let client = Client::new(base_url, "secret".into());
+        let puzzle = Puzzle::new(year!(2022), day!(12));
+
+        let submission = client.submit(puzzle, 2, "42").unwrap();
+        assert_eq!(submission.verdict, Verdict::Incorrect(Some(Hint::TooHigh)));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2022/day/12/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let submission = Submission::from_html("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>");
+        assert_eq!(
+            submission.verdict,
+            Verdict::TooRecent(Some(Duration::from_secs(65)))
+        );
+
+        let submission = Submission::from_html("<article><p>That's the right answer! You are <span>one gold star</span> closer.</p></article>");
+        assert_eq!(submission.verdict, Verdict::Correct);
+
+        assert_eq!(
+            parse_wait("You have 30s left to wait."),
+            Some(Duration::from_secs(30))
+        );
+    }
+}
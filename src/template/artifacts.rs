@@ -0,0 +1,251 @@
+/// Builds all solution binaries with a single `cargo build`, so that `run_multi` can execute them from `target/` without going through cargo once per day.
+/// Cargo reports the executable of each bin and every compiler diagnostic as JSON, one message per line.
+/// Compile errors are attributed to the bin whose source file caused them, which also works for errors reported while compiling the solution registry of the runner.
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{cargo_command, Puzzle, ANSI_BOLD, ANSI_RESET};
+
+/// The cargo profile that solution binaries are built with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    Debug,
+    Release,
+    /// Release build with heap profiling, see the `dhat-heap` feature.
+    Dhat,
+}
+
+impl Profile {
+    pub fn new(is_release: bool, is_dhat: bool) -> Self {
+        if is_dhat {
+            Profile::Dhat
+        } else if is_release {
+            Profile::Release
+        } else {
+            Profile::Debug
+        }
+    }
+
+    /// Arguments that select the profile for `cargo build` and `cargo run`.
+    pub fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            Profile::Debug => &[],
+            Profile::Release => &["--release"],
+            Profile::Dhat => &["--profile", "dhat", "--features", "dhat-heap"],
+        }
+    }
+}
+
+/// The result of building all solution binaries.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Artifacts {
+    /// Paths of the built executables, by the name of their bin.
+    executables: HashMap<String, PathBuf>,
+    /// Rendered compile errors by the bin that caused them, or by the target if they were not caused by a bin.
+    /// Each error is listed once, in the order cargo reported them.
+    errors: Vec<(String, Vec<String>)>,
+}
+
+impl Artifacts {
+    pub fn executable(&self, puzzle: Puzzle) -> Option<&Path> {
+        self.executables
+            .get(&puzzle.bin_name())
+            .map(PathBuf::as_path)
+    }
+
+    pub fn has_errors(&self, puzzle: Puzzle) -> bool {
+        let bin_name = puzzle.bin_name();
+        self.errors.iter().any(|(source, _)| *source == bin_name)
+    }
+
+    /// Print the compile errors of each bin and target, if any.
+    pub fn print_errors(&self) {
+        for (source, errors) in &self.errors {
+            println!("{ANSI_BOLD}Failed to compile {source}{ANSI_RESET}");
+            println!("------");
+            // rendered errors end with an empty line.
+            for error in errors {
+                print!("{error}");
+            }
+        }
+    }
+
+    fn add_error(&mut self, source: String, rendered: String) {
+        match self.errors.iter_mut().find(|(s, _)| *s == source) {
+            Some((_, errors)) if errors.contains(&rendered) => {}
+            Some((_, errors)) => errors.push(rendered),
+            None => self.errors.push((source, vec![rendered])),
+        }
+    }
+}
+
+/// Build all bins with `profile`. Bins that fail to compile are reported in the returned artifacts.
+/// Returns an error if the build failed for any other reason, e.g. an invalid manifest.
+pub fn build(profile: Profile) -> Result<Artifacts, String> {
+    let output = cargo_command()
+        .args([
+            "build",
+            "--quiet",
+            "--bins",
+            "--keep-going",
+            "--message-format=json-diagnostic-rendered-ansi",
+        ])
+        .args(profile.cargo_args())
+        .output()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+
+    let artifacts = parse_messages(&String::from_utf8_lossy(&output.stdout))?;
+
+    if !output.status.success() && artifacts.errors.is_empty() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(artifacts)
+}
+
+fn parse_messages(s: &str) -> Result<Artifacts, String> {
+    let mut artifacts = Artifacts::default();
+
+    for line in s.lines().filter(|line| line.starts_with('{')) {
+        let json = JsonValue::from_str(line).or(Err("Cargo message is not valid JSON."))?;
+        let Some(message) = json.get::<HashMap<String, JsonValue>>() else {
+            continue;
+        };
+
+        let string = |v: Option<&JsonValue>| v.and_then(|v| v.get::<String>()).cloned();
+        let target = message
+            .get("target")
+            .and_then(|t| t.get::<HashMap<String, JsonValue>>());
+        let target_name = string(target.and_then(|t| t.get("name")));
+
+        match string(message.get("reason")).as_deref() {
+            Some("compiler-artifact") => {
+                let is_bin = target
+                    .and_then(|t| t.get("kind"))
+                    .and_then(|kind| kind.get::<Vec<JsonValue>>())
+                    .is_some_and(|kind| {
+                        kind.iter()
+                            .any(|k| k.get::<String>().is_some_and(|k| k == "bin"))
+                    });
+
+                if let (true, Some(name), Some(executable)) =
+                    (is_bin, target_name, string(message.get("executable")))
+                {
+                    artifacts.executables.insert(name, executable.into());
+                }
+            }
+            Some("compiler-message") => {
+                let Some(diagnostic) = message
+                    .get("message")
+                    .and_then(|m| m.get::<HashMap<String, JsonValue>>())
+                else {
+                    continue;
+                };
+
+                if string(diagnostic.get("level")).as_deref() != Some("error") {
+                    continue;
+                }
+
+                let source = find_bin(diagnostic)
+                    .or(target_name)
+                    .unwrap_or_else(|| "unknown target".into());
+                let rendered = string(diagnostic.get("rendered"))
+                    .or_else(|| string(diagnostic.get("message")))
+                    .unwrap_or_default();
+
+                artifacts.add_error(source, rendered);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(artifacts)
+}
+
+/// The name of the bin whose source file is the primary location of a diagnostic, e.g. `2023-01` for `src/bin/2023-01.rs`.
+fn find_bin(diagnostic: &HashMap<String, JsonValue>) -> Option<String> {
+    let spans = diagnostic.get("spans")?.get::<Vec<JsonValue>>()?;
+
+    spans
+        .iter()
+        .filter_map(|span| span.get::<HashMap<String, JsonValue>>())
+        .find(|span| span.get("is_primary").and_then(|p| p.get::<bool>()) == Some(&true))
+        .and_then(|span| span.get("file_name")?.get::<String>())
+        .map(Path::new)
+        .filter(|path| path.parent().is_some_and(|dir| dir.ends_with("src/bin")))
+        .and_then(|path| path.file_stem()?.to_str().map(String::from))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_messages, Artifacts};
+    use crate::{day, template::Puzzle, year};
+    use std::path::Path;
+
+    #[test]
+    fn parses_cargo_messages() {
+        let messages = r#"
+{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null}
+{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"2023-01"},"executable":"/target/debug/2023-01"}
+{"reason":"compiler-message","target":{"kind":["lib"],"name":"advent_of_code"},"message":{"level":"error","rendered":"error: mismatched types\n","spans":[{"file_name":"/repo/src/bin/2023-02.rs","is_primary":true}]}}
+{"reason":"compiler-message","target":{"kind":["bin"],"name":"2023-02"},"message":{"level":"error","rendered":"error: mismatched types\n","spans":[{"file_name":"src/bin/2023-02.rs","is_primary":true}]}}
+{"reason":"compiler-message","target":{"kind":["lib"],"name":"advent_of_code"},"message":{"level":"error","rendered":"error: cannot find value\n","spans":[{"file_name":"src/lib.rs","is_primary":true}]}}
+{"reason":"compiler-message","target":{"kind":["lib"],"name":"advent_of_code"},"message":{"level":"warning","rendered":"warning: unused variable\n","spans":[]}}
+{"reason":"build-finished","success":false}
+"#;
+
+        let artifacts: Artifacts = parse_messages(messages).unwrap();
+        let puzzle = |day| Puzzle::new(year!(2023), day);
+
+        assert_eq!(
+            artifacts.executable(puzzle(day!(1))),
+            Some(Path::new("/target/debug/2023-01"))
+        );
+        assert_eq!(artifacts.executable(puzzle(day!(2))), None);
+        assert!(!artifacts.has_errors(puzzle(day!(1))));
+        assert!(artifacts.has_errors(puzzle(day!(2))));
+        assert_eq!(
+            artifacts.errors,
+            vec![
+                ("2023-02".into(), vec!["error: mismatched types\n".into()]),
+                (
+                    "advent_of_code".into(),
+                    vec!["error: cannot find value\n".into()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_executables_of_other_bins() {
+        // `--keep-going` builds the remaining bins after one of them failed to compile.
+        let messages = r#"
+{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"2023-01"},"executable":"/target/debug/2023-01"}
+{"reason":"compiler-message","target":{"kind":["bin"],"name":"2023-02"},"message":{"level":"error","rendered":"error: mismatched types\n","spans":[{"file_name":"src/bin/2023-02.rs","is_primary":true}]}}
+{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"2023-03"},"executable":"/target/debug/2023-03"}
+{"reason":"build-finished","success":false}
+"#;
+
+        let artifacts: Artifacts = parse_messages(messages).unwrap();
+        let puzzle = |day| Puzzle::new(year!(2023), day);
+
+        for (day, executable) in [
+            (day!(1), "/target/debug/2023-01"),
+            (day!(3), "/target/debug/2023-03"),
+        ] {
+            assert!(!artifacts.has_errors(puzzle(day)));
+            assert_eq!(
+                artifacts.executable(puzzle(day)),
+                Some(Path::new(executable))
+            );
+        }
+        assert!(artifacts.has_errors(puzzle(day!(2))));
+        assert_eq!(artifacts.executable(puzzle(day!(2))), None);
+    }
+}
@@ -0,0 +1,284 @@
+/// Benchmark configuration and statistics.
+use std::{collections::HashMap, fmt::Display, time::Duration};
+use tinyjson::JsonValue;
+
+/// Controls how often a part is run when benching, see the `[bench]` table of the [configuration](crate::template::config).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate total duration of all samples.
+    pub target: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Number of unmeasured runs before sampling. Defaults to a tenth of the samples.
+    pub warmup: Option<u128>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            target: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+            warmup: None,
+        }
+    }
+}
+
+impl BenchConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_samples == 0 {
+            return Err("the minimum number of samples must be at least 1.".into());
+        }
+        if self.min_samples > self.max_samples {
+            return Err(format!(
+                "the minimum number of samples ({}) exceeds the maximum ({}).",
+                self.min_samples, self.max_samples
+            ));
+        }
+        Ok(())
+    }
+
+    /// Number of samples to take for a part that took `base_time` on its first run.
+    pub fn samples_for(&self, base_time: Duration) -> u128 {
+        (self.target.as_nanos() / base_time.as_nanos().max(10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+
+    /// Number of warmup runs before taking `samples` samples.
+    pub fn warmup_for(&self, samples: u128) -> u128 {
+        self.warmup.unwrap_or((samples / 10).clamp(1, 100))
+    }
+}
+
+/// If the median sample is this many times faster than the first run, state such as a cache likely survived between runs.
+const SUSPICIOUS_SPEEDUP: f64 = 100.0;
+
+/* -------------------------------------------------------------------------- */
+
+/// Summary statistics over the samples of a benched part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub warmup: u128,
+    pub mean_nanos: f64,
+    pub median_nanos: f64,
+    pub min_nanos: f64,
+    pub max_nanos: f64,
+    pub std_dev_nanos: f64,
+    pub p95_nanos: f64,
+    /// Number of samples outside of 1.5 times the interquartile range.
+    pub outliers: u128,
+}
+
+impl BenchStats {
+    /// Compute statistics for a non-empty set of samples.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(timers: &[Duration], warmup: u128) -> Self {
+        let mut nanos: Vec<f64> = timers.iter().map(|t| t.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+
+        let variance = if nanos.len() > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        Self {
+            samples: nanos.len() as u128,
+            warmup,
+            mean_nanos: mean,
+            median_nanos: percentile(&nanos, 0.5),
+            min_nanos: nanos[0],
+            max_nanos: nanos[nanos.len() - 1],
+            std_dev_nanos: variance.sqrt(),
+            p95_nanos: percentile(&nanos, 0.95),
+            outliers: nanos.iter().filter(|x| **x < lower || **x > upper).count() as u128,
+        }
+    }
+
+    /// The representative duration of the part, robust against outliers.
+    pub fn median(&self) -> Duration {
+        nanos_to_duration(self.median_nanos)
+    }
+
+    /// Whether the samples were orders of magnitude faster than the first run that took `base_time`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn is_much_faster_than(&self, base_time: Duration) -> bool {
+        base_time.as_nanos() as f64 > self.median_nanos.max(1.0) * SUSPICIOUS_SPEEDUP
+    }
+}
+
+impl Display for BenchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "median {:.1?} | mean {:.1?} | min {:.1?} | max {:.1?} | σ {:.1?} | p95 {:.1?} | {} outliers",
+            nanos_to_duration(self.median_nanos),
+            nanos_to_duration(self.mean_nanos),
+            nanos_to_duration(self.min_nanos),
+            nanos_to_duration(self.max_nanos),
+            nanos_to_duration(self.std_dev_nanos),
+            nanos_to_duration(self.p95_nanos),
+            self.outliers
+        )
+    }
+}
+
+/// Linearly interpolated percentile of sorted values.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos_to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("warmup".into(), JsonValue::Number(value.warmup as f64));
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("max_nanos".into(), JsonValue::Number(value.max_nanos));
+        map.insert(
+            "std_dev_nanos".into(),
+            JsonValue::Number(value.std_dev_nanos),
+        );
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95_nanos));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            warmup: number("warmup")? as u128,
+            mean_nanos: number("mean_nanos")?,
+            median_nanos: number("median_nanos")?,
+            min_nanos: number("min_nanos")?,
+            max_nanos: number("max_nanos")?,
+            std_dev_nanos: number("std_dev_nanos")?,
+            p95_nanos: number("p95_nanos")?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/// Serialize optional stats, using `null` for parts that were not benched.
+pub fn stats_to_json(stats: Option<&BenchStats>) -> JsonValue {
+    stats.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/// Deserialize optional stats. Missing keys are treated like `null`.
+pub fn stats_from_json(value: Option<&JsonValue>) -> Result<Option<BenchStats>, String> {
+    match value {
+        Some(v) if !v.is_null() => BenchStats::try_from(v).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchConfig, BenchStats};
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|n| Duration::from_nanos(*n)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = BenchStats::from_samples(&nanos(&[50, 10, 30, 20, 40]), 2);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.warmup, 2);
+        assert_eq!(stats.mean_nanos, 30.0);
+        assert_eq!(stats.median_nanos, 30.0);
+        assert_eq!(stats.min_nanos, 10.0);
+        assert_eq!(stats.max_nanos, 50.0);
+        assert_eq!(stats.p95_nanos, 48.0);
+        assert!((stats.std_dev_nanos - 15.811_388).abs() < 1e-6);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn detects_outliers() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 1000]), 0);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median_nanos, 11.0);
+        assert!(stats.mean_nanos > 100.0);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&nanos(&[42]), 0);
+        assert_eq!(stats.median_nanos, 42.0);
+        assert_eq!(stats.std_dev_nanos, 0.0);
+    }
+
+    #[test]
+    fn detects_warm_samples() {
+        let stats = BenchStats::from_samples(&nanos(&[100, 110, 90]), 0);
+        assert!(stats.is_much_faster_than(Duration::from_millis(1)));
+        assert!(!stats.is_much_faster_than(Duration::from_nanos(500)));
+    }
+
+    #[test]
+    fn clamps_samples() {
+        let config = BenchConfig::default();
+        assert_eq!(config.samples_for(Duration::from_secs(2)), 10);
+        assert_eq!(config.samples_for(Duration::from_millis(1)), 1000);
+        assert_eq!(config.samples_for(Duration::from_nanos(1)), 10_000);
+        assert_eq!(config.warmup_for(1000), 100);
+        assert_eq!(config.warmup_for(10), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_sample_bounds() {
+        let config = BenchConfig {
+            min_samples: 100,
+            max_samples: 10,
+            ..BenchConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,204 @@
+/// Performance budgets for solutions, checked by `cargo time`.
+/// Each non-empty line of the budgets file has the format `<day> <part> <budget>`, e.g. `16 2 50ms`.
+/// The part is either `1`, `2`, `parse` or `total`. Lines starting with `#` are comments.
+use std::{fmt::Display, fs, str::FromStr, time::Duration};
+
+use crate::template::config::ConfigError;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, Year};
+
+static BUDGETS_FILE_NAME: &str = "budgets.txt";
+
+fn get_file_path(year: Year) -> Result<String, ConfigError> {
+    Ok(format!("{}/{BUDGETS_FILE_NAME}", year.data_dir()?))
+}
+
+/// The phase of a day that a budget applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part(u8),
+    Total,
+}
+
+impl Phase {
+    /// The measured duration of this phase in nanoseconds, if it was timed.
+    fn nanos(self, timing: &Timing) -> Option<f64> {
+        let stats = match self {
+            Phase::Parse => timing.parse_stats,
+            Phase::Part(1) => timing.part_1_stats,
+            Phase::Part(_) => timing.part_2_stats,
+            Phase::Total => return Some(timing.total_nanos).filter(|nanos| *nanos > 0.0),
+        };
+
+        stats.map(|stats| stats.median_nanos)
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(part) => write!(f, "part {part}"),
+            Phase::Total => write!(f, "total"),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "1" => Ok(Phase::Part(1)),
+            "2" => Ok(Phase::Part(2)),
+            "total" => Ok(Phase::Total),
+            s => Err(format!(
+                "unknown part `{s}`, expected `1`, `2`, `parse` or `total`."
+            )),
+        }
+    }
+}
+
+/// The maximum duration of a phase of a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Budget {
+    pub day: Day,
+    pub phase: Phase,
+    pub limit: Duration,
+}
+
+/// A measured duration that exceeds its budget.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Violation {
+    pub budget: Budget,
+    pub measured: Duration,
+}
+
+/// Read all budgets from the budgets file of `year`. If not present, returns no budgets.
+pub fn read_from_file(year: Year) -> Result<Vec<Budget>, String> {
+    let path = get_file_path(year).map_err(|e| e.to_string())?;
+
+    let Ok(s) = fs::read_to_string(&path) else {
+        return Ok(vec![]);
+    };
+
+    parse_lines(&s).map_err(|e| format!("{path}:{e}"))
+}
+
+fn parse_lines(s: &str) -> Result<Vec<Budget>, String> {
+    s.lines()
+        .enumerate()
+        .map(|(index, line)| (index, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(index, line)| parse_line(line).map_err(|e| format!("{}: {e}", index + 1)))
+        .collect()
+}
+
+fn parse_line(line: &str) -> Result<Budget, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+
+    let [day, phase, limit] = fields[..] else {
+        return Err("expected `<day> <part> <budget>`.".into());
+    };
+
+    Ok(Budget {
+        day: day.parse().map_err(|_| format!("invalid day `{day}`."))?,
+        phase: phase.parse()?,
+        limit: parse_duration(limit)?,
+    })
+}
+
+/// Parse a duration with a unit, e.g. `50ms` or `1.5s`.
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let nanos_per_unit = match unit {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => {
+            return Err(format!(
+                "invalid budget `{s}`, expected a unit of ns, µs, ms or s."
+            ))
+        }
+    };
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid budget `{s}`."))?;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok(Duration::from_nanos((value * nanos_per_unit) as u64))
+}
+
+/// Check the measured timings against their budgets. Phases that were not timed are skipped.
+pub fn check(budgets: &[Budget], timings: &Timings) -> Vec<Violation> {
+    budgets
+        .iter()
+        .filter_map(|budget| {
+            let timing = timings.data.iter().find(|t| t.day == budget.day)?;
+            let nanos = budget.phase.nanos(timing)?;
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let measured = Duration::from_nanos(nanos as u64);
+
+            (measured > budget.limit).then_some(Violation {
+                budget: *budget,
+                measured,
+            })
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, parse_duration, parse_lines, Phase};
+    use crate::day;
+    use crate::template::timings::{Timing, Timings};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_budgets() {
+        let s = "# day part budget\n16 2 50ms\n\n5 parse 1.5µs\n7 total 1s\n";
+        let budgets = parse_lines(s).unwrap();
+        assert_eq!(budgets.len(), 3);
+        assert_eq!(budgets[0].day, day!(16));
+        assert_eq!(budgets[0].phase, Phase::Part(2));
+        assert_eq!(budgets[0].limit, Duration::from_millis(50));
+        assert_eq!(budgets[1].phase, Phase::Parse);
+        assert_eq!(budgets[1].limit, Duration::from_nanos(1500));
+        assert_eq!(budgets[2].phase, Phase::Total);
+    }
+
+    #[test]
+    fn rejects_malformed_budgets() {
+        assert!(parse_lines("16 3 50ms").is_err());
+        assert!(parse_lines("26 1 50ms").is_err());
+        assert!(parse_lines("16 1 50").is_err());
+        assert!(parse_lines("16 1").is_err());
+        assert!(parse_duration("ms").is_err());
+    }
+
+    #[test]
+    fn checks_timings() {
+        let budgets = parse_lines("1 total 1ms\n1 1 1ms\n2 total 1ms").unwrap();
+        let timing = Timing {
+            total_nanos: 2e6,
+            ..Timing::from_records(day!(1), &[])
+        };
+        let timings = Timings { data: vec![timing] };
+
+        let violations = check(&budgets, &timings);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].budget.phase, Phase::Total);
+        assert_eq!(violations[0].measured, Duration::from_millis(2));
+    }
+}
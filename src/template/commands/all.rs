@@ -1,5 +1,20 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{collections::HashSet, process};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::artifacts::Profile;
+use crate::template::run_multi::{run_multi, Execution};
+use crate::template::{all_days, Day, Year};
+
+pub fn handle(year: Year, days: &[Day], profile: Profile, execution: Execution) {
+    let days_to_run: HashSet<Day> = if days.is_empty() {
+        all_days().collect()
+    } else {
+        days.iter().copied().collect()
+    };
+
+    let report = run_multi(year, &days_to_run, profile, false, execution);
+
+    if report.failures > 0 {
+        eprintln!("\n{} day(s) failed.", report.failures);
+        process::exit(1);
+    }
 }
@@ -0,0 +1,231 @@
+use std::{fs, path::Path, process};
+
+use crate::template::answers::Answers;
+use crate::template::commands::data_path;
+use crate::template::examples::{self, ExampleAnswer};
+use crate::template::records::{PartRecord, PartStatus};
+use crate::template::timings::Timing;
+use crate::template::{registry, runner, table, Puzzle, ANSI_BOLD, ANSI_RESET};
+
+/// A part of a solution that ran against one of the inputs.
+struct Cell {
+    record: Option<PartRecord>,
+    expected: Option<String>,
+    duration: Option<String>,
+}
+
+impl Cell {
+    fn answer(&self) -> Option<&str> {
+        self.record
+            .as_ref()
+            .filter(|record| record.status == PartStatus::Solved)
+            .and_then(|record| record.answer.as_deref())
+    }
+
+    fn is_mismatch(&self) -> bool {
+        self.expected
+            .as_deref()
+            .is_some_and(|expected| self.answer() != Some(expected))
+    }
+
+    fn format(&self) -> String {
+        let actual = match (&self.record, self.answer()) {
+            (_, Some(answer)) => format_answer(answer),
+            (Some(record), None) => record.status.as_str().replace('_', " "),
+            (None, None) => "-".into(),
+        };
+
+        match self.expected.as_deref() {
+            Some(expected) if self.is_mismatch() => {
+                format!("✖ {actual} (expected {})", format_answer(expected))
+            }
+            Some(_) => format!("✔ {actual}"),
+            None => actual,
+        }
+    }
+}
+
+struct Row {
+    /// The path of the input within the inputs directory, e.g. `08/alice.txt`.
+    file: String,
+    parts: [Cell; 2],
+}
+
+/// Run the solution of a day against its own input and all inputs in `data/<year>/inputs/<day>/`, then compare their answers to the expected answers.
+pub fn handle(puzzle: Puzzle, is_timed: bool, record: bool) {
+    if let Err(e) = registry::check() {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    let Some(solution) = registry::find(puzzle) else {
+        eprintln!(
+            "Solution \"{}\" does not exist, scaffold it first.",
+            puzzle.bin_path()
+        );
+        process::exit(1);
+    };
+
+    let files = find_inputs(puzzle);
+    if files.is_empty() {
+        eprintln!(
+            "No inputs found, add them to \"{}\".",
+            data_path(puzzle.inputs_path(&puzzle.day.to_string()))
+        );
+        process::exit(1);
+    }
+
+    let answers_path = data_path(puzzle.inputs_path(&format!("{}.answers", puzzle.day)));
+    let expected = fs::read_to_string(&answers_path)
+        .map(|s| examples::parse_answers(&s))
+        .unwrap_or_default();
+    let accepted = Answers::read_from_file(puzzle.year).unwrap_or_else(|e| {
+        eprintln!("Failed to read accepted answers: {e}");
+        process::exit(1);
+    });
+    let own_file = format!("{}.txt", puzzle.day);
+
+    let mut rows: Vec<Row> = vec![];
+
+    for (index, file) in files.into_iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Input {file}{ANSI_RESET}");
+        println!("------");
+
+        let records = match fs::read_to_string(data_path(puzzle.inputs_path(&file))) {
+            Ok(input) => runner::run_solution(solution, &input, is_timed),
+            Err(e) => {
+                eprintln!("could not open input file \"{file}\": {e}");
+                vec![]
+            }
+        };
+        let timing = Timing::from_records(puzzle.day, &records);
+
+        let parts = [1, 2].map(|part| {
+            // the accepted answers of the own input are its expected answers, unless listed in the answers file.
+            let expected = expected
+                .iter()
+                .find(|answer: &&ExampleAnswer| answer.file == file && answer.part == part)
+                .map(|answer| answer.expected.clone())
+                .or_else(|| {
+                    accepted
+                        .get(puzzle.day, part)
+                        .filter(|_| file == own_file)
+                        .map(String::from)
+                });
+
+            Cell {
+                record: records.iter().find(|r| r.part == part).cloned(),
+                expected,
+                duration: if part == 1 {
+                    timing.part_1.clone()
+                } else {
+                    timing.part_2.clone()
+                },
+            }
+        });
+
+        rows.push(Row { file, parts });
+    }
+
+    println!();
+    print_table(&rows);
+
+    if record {
+        match store_answers(&answers_path, &rows) {
+            Ok(()) => println!("\nStored expected answers in \"{answers_path}\"."),
+            Err(e) => {
+                eprintln!("\nFailed to store expected answers: {e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let mismatches: Vec<&str> = rows
+        .iter()
+        .filter(|row| row.parts.iter().any(Cell::is_mismatch))
+        .map(|row| row.file.as_str())
+        .collect();
+
+    if !mismatches.is_empty() {
+        eprintln!(
+            "\n{} input(s) do not match their expected answers: {}.",
+            mismatches.len(),
+            mismatches.join(", ")
+        );
+        process::exit(1);
+    }
+}
+
+/// The own input of the day, followed by the inputs in its directory, sorted by name.
+fn find_inputs(puzzle: Puzzle) -> Vec<String> {
+    let day = puzzle.day.to_string();
+    let own_file = format!("{day}.txt");
+
+    let mut shared: Vec<String> = fs::read_dir(data_path(puzzle.inputs_path(&day)))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter(|name| {
+                    Path::new(name)
+                        .extension()
+                        .is_some_and(|extension| extension == "txt")
+                })
+                .map(|name| format!("{day}/{name}"))
+                .collect()
+        })
+        .unwrap_or_default();
+    shared.sort_unstable();
+
+    let mut files = vec![];
+    if Path::new(&data_path(puzzle.inputs_path(&own_file))).exists() {
+        files.push(own_file);
+    }
+    files.extend(shared);
+    files
+}
+
+/// Replace the answers file with the current answers of all inputs.
+fn store_answers(path: &str, rows: &[Row]) -> Result<(), std::io::Error> {
+    let mut lines = vec![
+        "# Expected answers of the inputs, one per line: <input file> <part> <answer>".to_string(),
+    ];
+
+    for row in rows {
+        for (part, cell) in (1..).zip(&row.parts) {
+            // answers files hold a single line per answer.
+            if let Some(answer) = cell.answer().filter(|answer| !answer.contains('\n')) {
+                lines.push(format!("{} {part} {answer}", row.file));
+            }
+        }
+    }
+
+    fs::write(path, lines.join("\n") + "\n")
+}
+
+fn format_answer(answer: &str) -> String {
+    answer.replace('\n', "\\n")
+}
+
+fn print_table(rows: &[Row]) {
+    let cells: Vec<[String; 5]> = rows
+        .iter()
+        .map(|row| {
+            let [part_1, part_2] = &row.parts;
+            let duration = |cell: &Cell| cell.duration.clone().unwrap_or_else(|| "-".into());
+
+            [
+                row.file.clone(),
+                part_1.format(),
+                duration(part_1),
+                part_2.format(),
+                duration(part_2),
+            ]
+        })
+        .collect();
+
+    table::print(["Input", "Part 1", "Time", "Part 2", "Time"], &cells);
+}
@@ -1,6 +1,24 @@
+use std::process;
+
+pub use crate::template::artifacts::Profile;
+use crate::template::config::ConfigError;
+pub use crate::template::run_multi::Execution;
+
 pub mod all;
 pub mod download;
+pub mod inputs;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
 pub mod time;
+pub mod verify;
+pub mod watch;
+
+/// Unwrap a path in the data directory, resolving it only fails if the configuration is invalid.
+fn data_path(path: Result<String, ConfigError>) -> String {
+    path.unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
@@ -1,15 +1,93 @@
-use std::process;
+use std::{
+    env, fs,
+    process::{self, Command, Stdio},
+};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_client::{self, Backend};
+use crate::template::commands::data_path;
+use crate::template::{aoc_cli, config, puzzle, Puzzle};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+const DEFAULT_WIDTH: usize = 80;
+
+pub fn handle(puzzle: Puzzle, part: Option<u8>) {
+    if part.is_some_and(|part| part != 1 && part != 2) {
+        eprintln!("Unexpected command-line input. Format: cargo read 1 --part 2");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let puzzle_path = data_path(puzzle.puzzle_path());
+
+    // part two is only in the description once part one is solved, so a cached description might predate it.
+    let is_cached = read_part(&puzzle_path, part).is_some();
+
+    if !is_cached {
+        download_puzzle(puzzle);
+    }
+
+    let Some(markdown) = read_part(&puzzle_path, part) else {
+        eprintln!("Part two of {puzzle} is not unlocked yet.");
         process::exit(1);
     };
+
+    print!("{}", puzzle::render(&markdown, terminal_width()));
+}
+
+/// Read the cached description of one part, or the whole description if no part is given.
+fn read_part(puzzle_path: &str, part: Option<u8>) -> Option<String> {
+    let markdown = fs::read_to_string(puzzle_path).ok()?;
+
+    match part {
+        Some(part) => puzzle::select_part(&markdown, part).map(String::from),
+        None => Some(markdown),
+    }
+}
+
+/// Width to wrap the description at: `COLUMNS` if the shell exports it, otherwise the width of the terminal.
+fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .or_else(query_terminal_width)
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|columns| *columns > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Ask the terminal for its width via `tput cols`.
+/// The output of `tput` is captured, so it measures the terminal on stderr instead.
+fn query_terminal_width() -> Option<String> {
+    let output = Command::new("tput")
+        .arg("cols")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn download_puzzle(puzzle: Puzzle) {
+    match config::get().backend {
+        Backend::Native => {
+            if let Err(e) = aoc_client::refresh_puzzle(puzzle) {
+                eprintln!("failed to download puzzle for {puzzle}: {e}");
+                process::exit(1);
+            }
+        }
+        Backend::AocCli => {
+            if aoc_cli::check().is_err() {
+                eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+                process::exit(1);
+            }
+
+            if let Err(e) = aoc_cli::download_puzzle(puzzle) {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            };
+        }
+    }
+
+    println!();
 }
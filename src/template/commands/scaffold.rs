@@ -1,10 +1,13 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::commands::{data_path, download};
+use crate::template::examples::{self, Example, ExampleAnswer};
+use crate::template::{config, Puzzle};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -21,10 +24,18 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: Puzzle, download: bool) {
+    let input_path = data_path(puzzle.input_path());
+    let answers_path = data_path(puzzle.example_path(&format!("{}.answers", puzzle.day)));
+    let module_path = puzzle.bin_path();
+
+    for dir in ["inputs", "examples", "puzzles"] {
+        if let Err(e) = fs::create_dir_all(Path::new(&data_path(puzzle.year.data_dir())).join(dir))
+        {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -36,7 +47,7 @@ pub fn handle(day: Day) {
 
     match file.write_all(
         MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+            .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
             .as_bytes(),
     ) {
         Ok(()) => {
@@ -58,16 +69,149 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    if download {
+        download::handle(puzzle);
+    }
+
+    // the puzzle description is only present if it was downloaded.
+    let examples = fs::read_to_string(data_path(puzzle.puzzle_path()))
+        .map(|puzzle| examples::extract(&puzzle))
+        .unwrap_or_default();
+
+    let answers = create_example_files(puzzle, &examples);
+
+    match safe_create_file(&answers_path) {
+        Ok(mut file) => {
+            let header = format!(
+                "# Expected example answers, one per line: <example file> <part> <answer>\n# e.g. {}.txt 1 42\n",
+                puzzle.day
+            );
+            if let Err(e) = file.write_all((header + &answers).as_bytes()) {
+                eprintln!("Failed to write example answers file: {e}");
+                process::exit(1);
+            }
+            println!("Created example answers file \"{}\"", &answers_path);
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            if let Err(e) = append_missing_answers(&answers_path, &answers) {
+                eprintln!("Failed to update example answers file: {e}");
+                process::exit(1);
+            }
         }
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to create example answers file: {e}");
             process::exit(1);
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if config::get().year == Some(puzzle.year) {
+        println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day);
+    } else {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
+    }
+}
+
+fn get_example_file_name(puzzle: Puzzle, index: usize) -> String {
+    match index {
+        0 => format!("{}.txt", puzzle.day),
+        index => format!("{}-{}.txt", puzzle.day, index + 1),
+    }
+}
+
+/// Write the examples found in the puzzle description to example files, or an empty example file if there are none.
+/// Returns the lines of the answers file for the expected answers stated in the description.
+fn create_example_files(puzzle: Puzzle, examples: &[Example]) -> String {
+    if examples.is_empty() {
+        let example_path = data_path(puzzle.example_path(&get_example_file_name(puzzle, 0)));
+        match create_file(&example_path) {
+            Ok(_) => {
+                println!("Created empty example file \"{}\"", &example_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
+        }
+        return String::new();
+    }
+
+    let mut answers = String::new();
+
+    for (index, example) in examples.iter().enumerate() {
+        let file_name = get_example_file_name(puzzle, index);
+        let example_path = data_path(puzzle.example_path(&file_name));
+        match create_file(&example_path)
+            .and_then(|mut file| file.write_all(example.input.as_bytes()))
+        {
+            Ok(()) => {
+                println!(
+                    "Created example file \"{}\" from puzzle description",
+                    &example_path
+                );
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
+        }
+
+        for (part, answer) in &example.answers {
+            answers.push_str(&format!("{file_name} {part} {answer}\n"));
+        }
+    }
+
+    answers
+}
+
+/// Append the extracted answers for example files and parts that the answers file does not list yet.
+/// Answers that have already been written down are kept, even if the description states another one.
+fn append_missing_answers(answers_path: &str, answers: &str) -> Result<(), std::io::Error> {
+    let contents = fs::read_to_string(answers_path)?;
+    let existing = examples::parse_answers(&contents);
+    let find_existing = |answer: &ExampleAnswer| {
+        existing
+            .iter()
+            .find(|e| e.file == answer.file && e.part == answer.part)
+    };
+
+    let mut missing = String::new();
+
+    for answer in examples::parse_answers(answers) {
+        match find_existing(&answer) {
+            None => {
+                missing.push_str(&format!(
+                    "{} {} {}\n",
+                    answer.file, answer.part, answer.expected
+                ));
+            }
+            Some(e) if e.expected != answer.expected => println!(
+                "Kept answer {} of {} part {} in \"{answers_path}\", the description states {}",
+                e.expected, e.file, e.part, answer.expected
+            ),
+            Some(_) => {}
+        }
+    }
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let separator = if contents.is_empty() || contents.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+
+    let mut file = OpenOptions::new().append(true).open(answers_path)?;
+    file.write_all((separator.to_string() + &missing).as_bytes())?;
+    println!(
+        "Added {} example answer(s) to \"{answers_path}\"",
+        missing.lines().count()
+    );
+
+    Ok(())
 }
@@ -1,9 +1,16 @@
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 
-use crate::template::Day;
+use crate::template::{cargo_command, InputSource, Puzzle};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    puzzle: Puzzle,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    force: bool,
+    input: &InputSource,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -23,7 +30,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if force {
+        cmd_args.push("--force".to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
+    let mut cmd = cargo_command()
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
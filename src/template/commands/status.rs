@@ -0,0 +1,187 @@
+use std::{fs, process};
+
+use crate::template::answers::Answers;
+use crate::template::commands::data_path;
+use crate::template::examples::{self, ExampleAnswer};
+use crate::template::timings::Timings;
+use crate::template::{
+    all_days, registry, runner, table, Outcome, Puzzle, Solution, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// The state of a part of a scaffolded day.
+enum PartState {
+    /// The part returns the accepted answer.
+    Accepted,
+    /// The part returns an answer that differs from the accepted answer.
+    Changed,
+    /// The part returns an answer, but none has been accepted yet.
+    Unaccepted,
+    /// The part does not return an answer.
+    Unsolved,
+}
+
+impl PartState {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PartState::Accepted => "⭐ accepted",
+            PartState::Changed => "✖ changed",
+            PartState::Unaccepted => "? answer",
+            PartState::Unsolved => "-",
+        }
+    }
+}
+
+/// The progress of a single day.
+struct Row {
+    puzzle: Puzzle,
+    has_bin: bool,
+    has_input: bool,
+    has_examples: bool,
+    has_puzzle: bool,
+    /// Passed and total number of example answers.
+    examples_passed: Option<(usize, usize)>,
+    parts: Option<[PartState; 2]>,
+    is_timed: bool,
+}
+
+pub fn handle(year: Year) {
+    if let Err(e) = registry::check() {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    let answers = Answers::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read accepted answers: {e}");
+        process::exit(1);
+    });
+    let timings = Timings::read_from_file(year);
+
+    let rows: Vec<Row> = all_days()
+        .map(|day| {
+            let puzzle = Puzzle::new(year, day);
+            let solution = registry::find(puzzle);
+            let input = read_non_empty(&data_path(puzzle.input_path()));
+
+            Row {
+                puzzle,
+                has_bin: solution.is_some(),
+                has_input: input.is_some(),
+                has_examples: read_non_empty(&data_path(
+                    puzzle.example_path(&format!("{day}.txt")),
+                ))
+                .is_some(),
+                has_puzzle: read_non_empty(&data_path(puzzle.puzzle_path())).is_some(),
+                examples_passed: solution.map(|solution| check_examples(solution, puzzle)),
+                parts: solution
+                    .zip(input)
+                    .map(|(solution, input)| check_parts(solution, &input, &answers)),
+                is_timed: timings.data.iter().any(|t| t.day == day),
+            }
+        })
+        .collect();
+
+    print_table(&rows);
+
+    let stars = rows
+        .iter()
+        .flat_map(|row| row.parts.iter().flatten())
+        .filter(|state| matches!(state, PartState::Accepted))
+        .count();
+    println!("\n{ANSI_BOLD}Stars:{ANSI_RESET} {stars}/50");
+}
+
+fn read_non_empty(path: &str) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+}
+
+/// Run a solution against the example answers listed in the answers file of its day.
+fn check_examples(solution: &dyn Solution, puzzle: Puzzle) -> (usize, usize) {
+    let answers = fs::read_to_string(data_path(
+        puzzle.example_path(&format!("{}.answers", puzzle.day)),
+    ))
+    .map(|s| examples::parse_answers(&s))
+    .unwrap_or_default();
+
+    let mut files: Vec<&str> = answers.iter().map(|answer| answer.file.as_str()).collect();
+    files.sort_unstable();
+    files.dedup();
+
+    // each example is parsed once for all parts that list an answer for it.
+    let passed = files
+        .into_iter()
+        .map(|file| {
+            let Ok(input) = fs::read_to_string(data_path(puzzle.example_path(file))) else {
+                return 0;
+            };
+
+            let answers: Vec<&ExampleAnswer> = answers
+                .iter()
+                .filter(|answer| answer.file == file)
+                .collect();
+            let parts: Vec<u8> = answers.iter().map(|answer| answer.part).collect();
+
+            runner::run_solution_quietly(solution, &input, &parts)
+                .into_iter()
+                .zip(answers)
+                .filter(|(outcome, answer)| *outcome == Outcome::Answer(answer.expected.clone()))
+                .count()
+        })
+        .sum();
+
+    (passed, answers.len())
+}
+
+/// Run both parts of a solution and compare their answers to the accepted answers.
+fn check_parts(solution: &dyn Solution, input: &str, answers: &Answers) -> [PartState; 2] {
+    let outcomes = runner::run_solution_quietly(solution, input, &[1, 2]);
+
+    [1, 2].map(|part| {
+        let outcome = &outcomes[usize::from(part) - 1];
+        let accepted = answers.get(solution.day(), part);
+
+        match (outcome, accepted) {
+            (Outcome::Answer(answer), Some(accepted)) if answer == accepted => PartState::Accepted,
+            (Outcome::Answer(_), Some(_)) => PartState::Changed,
+            (Outcome::Answer(_), None) => PartState::Unaccepted,
+            _ => PartState::Unsolved,
+        }
+    })
+}
+
+fn print_table(rows: &[Row]) {
+    let check = |value: bool| if value { "✔" } else { "-" }.to_string();
+
+    let cells: Vec<[String; 9]> = rows
+        .iter()
+        .map(|row| {
+            let [part_1, part_2] = row
+                .parts
+                .as_ref()
+                .map_or(["-", "-"], |parts| parts.each_ref().map(PartState::as_str));
+
+            [
+                row.puzzle.day.to_string(),
+                check(row.has_bin),
+                check(row.has_input),
+                check(row.has_examples),
+                check(row.has_puzzle),
+                match row.examples_passed {
+                    Some((_, 0)) | None => "-".into(),
+                    Some((passed, total)) => format!("{passed}/{total}"),
+                },
+                part_1.into(),
+                part_2.into(),
+                check(row.is_timed),
+            ]
+        })
+        .collect();
+
+    table::print(
+        [
+            "Day", "Bin", "Input", "Examples", "Puzzle", "Tests", "Part 1", "Part 2", "Timed",
+        ],
+        &cells,
+    );
+}
@@ -1,11 +1,30 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::artifacts::Profile;
+use crate::template::budgets;
+use crate::template::history::{self, Run};
+use crate::template::run_multi::{run_multi, Execution};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Options for comparing a run against an earlier run from the timing history.
+pub struct Compare {
+    /// Name or commit of the run to compare against. Defaults to the latest run of each day.
+    pub baseline: Option<String>,
+    /// Slowdown in percent that is reported as a regression.
+    pub threshold: f64,
+}
+
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    execution: Execution,
+    name: Option<String>,
+    compare: Option<Compare>,
+) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
@@ -21,14 +40,36 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let budgets = match budgets::read_from_file(year) {
+        Ok(budgets) => budgets,
+        Err(e) => {
+            eprintln!("Failed to read budgets: {e}");
+            process::exit(1);
+        }
+    };
+
+    let report = run_multi(year, &days_to_run, Profile::Release, true, execution);
+    let timings = report.timings.unwrap();
+
+    if let Some(compare) = compare {
+        match history::read_from_file(year) {
+            Ok(runs) => print_comparison(&timings, &runs, &compare),
+            Err(e) => eprintln!("Failed to read timing history: {e}"),
+        }
+    }
+
+    if !timings.data.is_empty() {
+        if let Err(e) = history::append(year, &Run::now(name, timings.data.clone())) {
+            eprintln!("Failed to append run to timing history: {e}");
+        }
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +78,89 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    let violations = budgets::check(&budgets, &timings);
+
+    if report.failures > 0 {
+        eprintln!("\n{} day(s) failed.", report.failures);
+    }
+
+    if !violations.is_empty() {
+        eprintln!();
+        for violation in &violations {
+            let budget = violation.budget;
+            eprintln!(
+                "Day {} {}: {:.1?} exceeds the budget of {:?}.",
+                budget.day, budget.phase, violation.measured, budget.limit
+            );
+        }
+        eprintln!("\n{} budget(s) exceeded.", violations.len());
+    }
+
+    if report.failures > 0 || !violations.is_empty() {
+        process::exit(1);
+    }
+}
+
+fn print_comparison(timings: &Timings, runs: &[Run], compare: &Compare) {
+    println!();
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET}");
+    println!("------");
+
+    if let Some(baseline) = &compare.baseline {
+        match runs.iter().rev().find(|run| run.matches(baseline)) {
+            Some(run) => println!("Baseline: {}", run.describe()),
+            None => {
+                eprintln!("No run named `{baseline}` found in the timing history.");
+                return;
+            }
+        }
+    }
+
+    let mut regressions = 0;
+
+    for timing in &timings.data {
+        let baseline = history::find_baseline(runs, timing.day, compare.baseline.as_deref())
+            .and_then(|run| run.timing(timing.day).map(|t| (run, t)));
+
+        let Some((run, baseline_timing)) = baseline.filter(|(_, t)| t.total_nanos > 0.0) else {
+            println!("Day {}: no baseline", timing.day);
+            continue;
+        };
+
+        let delta = (timing.total_nanos / baseline_timing.total_nanos - 1.0) * 100.0;
+
+        let flag = if delta > compare.threshold {
+            regressions += 1;
+            " ▲ regression"
+        } else if delta < -compare.threshold {
+            " ▼ improvement"
+        } else {
+            ""
+        };
+
+        let since = if compare.baseline.is_none() {
+            format!(" since {}", run.describe())
+        } else {
+            String::new()
+        };
+
+        println!(
+            "Day {}: {} → {} ({delta:+.1}%){flag}{since}",
+            timing.day,
+            format_total(baseline_timing),
+            format_total(timing),
+        );
+    }
+
+    if regressions > 0 {
+        println!(
+            "\n{regressions} day(s) are more than {}% slower than their baseline.",
+            compare.threshold
+        );
+    }
+}
+
+fn format_total(timing: &Timing) -> String {
+    format!("{:.2}ms", timing.total_nanos / 1_000_000_f64)
 }
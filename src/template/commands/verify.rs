@@ -0,0 +1,139 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::records::PartStatus;
+use crate::template::run_multi::{in_process, DayOutcome};
+use crate::template::{all_days, registry, table, Day, Puzzle, Year, ANSI_BOLD, ANSI_RESET};
+
+enum Verdict {
+    Pass,
+    Fail,
+    Unrecorded,
+    Recorded,
+}
+
+impl Verdict {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "✔ pass",
+            Verdict::Fail => "✖ fail",
+            Verdict::Unrecorded => "- unrecorded",
+            Verdict::Recorded => "+ recorded",
+        }
+    }
+}
+
+struct Row {
+    day: Day,
+    part: u8,
+    expected: Option<String>,
+    actual: Option<String>,
+    verdict: Verdict,
+}
+
+pub fn handle(year: Year, days: &[Day], record: bool) {
+    if let Err(e) = registry::check() {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    // a file that cannot be read would otherwise be replaced by the answers of this run.
+    let mut answers = Answers::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read accepted answers: {e}");
+        process::exit(1);
+    });
+
+    let days_to_run: Vec<Day> = if days.is_empty() {
+        all_days()
+            .filter(|day| registry::find(Puzzle::new(year, *day)).is_some())
+            .collect()
+    } else {
+        days.to_vec()
+    };
+
+    let mut rows: Vec<Row> = vec![];
+
+    for day in days_to_run {
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let outcome = in_process::run_solution(Puzzle::new(year, day), false);
+        let records = outcome.records();
+        if let DayOutcome::Failed(reason, records) = &outcome {
+            // failures of parts are already reported by the runner.
+            if records.is_empty() {
+                eprintln!("{reason}");
+            }
+        }
+        println!();
+
+        for part in [1, 2] {
+            let actual = records
+                .iter()
+                .find(|r| r.part == part && r.status == PartStatus::Solved)
+                .and_then(|r| r.answer.clone());
+            let expected = answers.get(day, part).map(String::from);
+
+            let verdict = match (&expected, &actual) {
+                (_, Some(actual)) if record => {
+                    answers.set(day, part, actual);
+                    Verdict::Recorded
+                }
+                (Some(expected), Some(actual)) if expected == actual => Verdict::Pass,
+                (Some(_), _) => Verdict::Fail,
+                (None, _) => Verdict::Unrecorded,
+            };
+
+            rows.push(Row {
+                day,
+                part,
+                expected,
+                actual,
+                verdict,
+            });
+        }
+    }
+
+    print_table(&rows);
+
+    if record {
+        match answers.store_file(year) {
+            Ok(()) => println!("\nStored accepted answers."),
+            Err(e) => {
+                eprintln!("\nFailed to store accepted answers: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let failed = rows
+        .iter()
+        .filter(|row| matches!(row.verdict, Verdict::Fail))
+        .count();
+
+    if failed > 0 {
+        eprintln!("\n{failed} part(s) do not match their accepted answer.");
+        process::exit(1);
+    }
+}
+
+fn format_answer(answer: Option<&String>) -> String {
+    answer.map_or_else(|| "-".into(), |answer| answer.replace('\n', "\\n"))
+}
+
+fn print_table(rows: &[Row]) {
+    let cells: Vec<[String; 5]> = rows
+        .iter()
+        .map(|row| {
+            [
+                row.day.to_string(),
+                row.part.to_string(),
+                row.verdict.as_str().into(),
+                format_answer(row.expected.as_ref()),
+                format_answer(row.actual.as_ref()),
+            ]
+        })
+        .collect();
+
+    table::print(["Day", "Part", "Result", "Expected", "Actual"], &cells);
+}
@@ -0,0 +1,180 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{self, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::commands::data_path;
+use crate::template::records::PartRecord;
+use crate::template::run_multi::{child_commands, DayOutcome};
+use crate::template::{cargo_command, Puzzle, ANSI_BOLD, ANSI_RESET};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Editors often write a file in several steps, wait this long for them to finish before running.
+const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Modification times of all watched files, changes whenever a file is changed, added or removed.
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// Re-run the example tests and / or the solution of a day whenever its code or data changes, until interrupted.
+pub fn handle(puzzle: Puzzle, run_tests: bool, run_solve: bool, is_release: bool) {
+    if !Path::new(&puzzle.bin_path()).exists() {
+        eprintln!(
+            "Solution \"{}\" does not exist, scaffold it first.",
+            puzzle.bin_path()
+        );
+        process::exit(1);
+    }
+
+    let mut previous: Vec<PartRecord> = vec![];
+    let mut snapshot = take_snapshot(puzzle);
+
+    loop {
+        print!("{ANSI_CLEAR_SCREEN}");
+
+        if run_tests {
+            println!("{ANSI_BOLD}Tests{ANSI_RESET}");
+            println!("------");
+            run_tests_once(puzzle, is_release);
+            println!();
+        }
+
+        if run_solve {
+            println!("{ANSI_BOLD}Solution{ANSI_RESET}");
+            println!("------");
+            match child_commands::run_solution(puzzle, false, is_release) {
+                Ok(outcome) => {
+                    if let DayOutcome::Failed(reason, _) = &outcome {
+                        println!("Failed: {reason}");
+                    }
+                    print_answers(&previous, outcome.records());
+                    previous = outcome.records().to_vec();
+                }
+                Err(e) => eprintln!("Failed to run solution: {e}"),
+            }
+            println!();
+        }
+
+        println!("Watching {puzzle} for changes, press Ctrl+C to stop.");
+        snapshot = wait_for_change(puzzle, &snapshot);
+    }
+}
+
+fn run_tests_once(puzzle: Puzzle, is_release: bool) {
+    let bin_name = puzzle.bin_name();
+    let mut args = vec!["test", "--quiet", "--bin", &bin_name];
+
+    if is_release {
+        args.push("--release");
+    }
+
+    let status = cargo_command()
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    match status {
+        Ok(status) if status.success() => println!("Tests passed."),
+        Ok(_) => println!("Tests failed."),
+        Err(e) => eprintln!("Failed to run tests: {e}"),
+    }
+}
+
+/// Print the answer of each part next to the answer of the previous run.
+fn print_answers(previous: &[PartRecord], current: &[PartRecord]) {
+    if previous.is_empty() {
+        return;
+    }
+
+    let answer = |records: &[PartRecord], part: u8| {
+        records
+            .iter()
+            .find(|r| r.part == part)
+            .and_then(|r| r.answer.clone())
+    };
+
+    println!();
+    for part in [1, 2] {
+        let before = answer(previous, part);
+        let after = answer(current, part);
+
+        let change = if before == after {
+            "unchanged".into()
+        } else {
+            format!("was {}", before.as_deref().unwrap_or("-"))
+        };
+
+        println!(
+            "Part {part}: {} ({change})",
+            after.as_deref().unwrap_or("-")
+        );
+    }
+}
+
+fn wait_for_change(puzzle: Puzzle, snapshot: &Snapshot) -> Snapshot {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        if take_snapshot(puzzle) != *snapshot {
+            thread::sleep(SETTLE_DELAY);
+            return take_snapshot(puzzle);
+        }
+    }
+}
+
+/// The solution, the template and library code, as well as the inputs and examples of the day.
+fn take_snapshot(puzzle: Puzzle) -> Snapshot {
+    let mut paths = vec![
+        PathBuf::from(puzzle.bin_path()),
+        PathBuf::from("src/lib.rs"),
+    ];
+    collect_files(Path::new("src/template"), &mut paths);
+
+    let day = puzzle.day.to_string();
+    let data_dir = PathBuf::from(data_path(puzzle.year.data_dir()));
+    for folder in ["inputs", "examples"] {
+        let Ok(entries) = fs::read_dir(data_dir.join(folder)) else {
+            continue;
+        };
+
+        paths.extend(
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(&day))
+                }),
+        );
+    }
+
+    paths.sort_unstable();
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+        if path.is_dir() {
+            collect_files(&path, paths);
+        } else {
+            paths.push(path);
+        }
+    }
+}
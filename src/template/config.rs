@@ -0,0 +1,369 @@
+/// Project configuration, read once from `aoc.toml` in the repository root.
+/// Every setting has a default, a missing file is the same as an empty one.
+/// Some settings can be overridden with environment variables, see [`ENV_OVERRIDES`].
+use std::{env, fmt::Display, fs, io, sync::OnceLock, time::Duration};
+
+use crate::template::aoc_client::Backend;
+use crate::template::bench::BenchConfig;
+use crate::template::config_file;
+use crate::template::{readme_benchmarks, readme_stars, Year};
+
+pub use crate::template::config_file::CONFIG_FILE_NAME;
+
+/// Environment variables and the settings they override.
+pub static ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("AOC_YEAR", "year"),
+    ("AOC_DATA_DIR", "data_dir"),
+    ("AOC_README", "readme.path"),
+    ("AOC_BENCH_TARGET_MS", "bench.target_ms"),
+    ("AOC_BENCH_MIN_SAMPLES", "bench.min_samples"),
+    ("AOC_BENCH_MAX_SAMPLES", "bench.max_samples"),
+    ("AOC_BENCH_WARMUP", "bench.warmup"),
+    ("AOC_BACKEND", "backend"),
+    ("AOC_TIMEOUT_SECS", "run.timeout_secs"),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// The year of commands that are not passed `--year`.
+    pub year: Option<Year>,
+    /// The directory that holds one directory of data per year.
+    pub data_dir: String,
+    /// How to talk to the Advent of Code website.
+    pub backend: Backend,
+    pub readme: ReadmeConfig,
+    pub bench: BenchConfig,
+    pub submit: SubmitConfig,
+    pub run: RunConfig,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadmeConfig {
+    pub path: String,
+    /// Encloses the benchmarks table updated by `cargo time --store`.
+    pub benchmarks_marker: String,
+    /// Encloses the results table updated after a correct submission.
+    pub stars_marker: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubmitConfig {
+    /// Refuse answers that the submission ledger rules out, unless `--force` is passed.
+    pub check_ledger: bool,
+    /// Add a star to the results table in the readme after a correct submission.
+    pub update_readme: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RunConfig {
+    /// Stop days of `cargo all` and `cargo time` that run for longer than this, unless `--timeout` is passed.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            data_dir: "data".into(),
+            backend: Backend::Native,
+            readme: ReadmeConfig {
+                path: "README.md".into(),
+                benchmarks_marker: readme_benchmarks::MARKER.into(),
+                stars_marker: readme_stars::MARKER.into(),
+            },
+            bench: BenchConfig::default(),
+            submit: SubmitConfig {
+                check_ledger: true,
+                update_readme: true,
+            },
+            run: RunConfig { timeout: None },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    IO(io::Error),
+    /// An invalid setting, with the place it was read from.
+    Invalid(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::IO(e) => write!(f, "failed to read {CONFIG_FILE_NAME}: {e}"),
+            ConfigError::Invalid(e) => write!(f, "invalid configuration: {e}"),
+        }
+    }
+}
+
+impl From<ConfigError> for io::Error {
+    fn from(e: ConfigError) -> Self {
+        io::Error::other(e.to_string())
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Load the configuration, or return it if it is loaded already.
+pub fn init() -> Result<&'static Config, ConfigError> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+
+    let config = Config::load()?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// The configuration of this process, which the runner and the solution binaries load with [`init`] when they start.
+/// Panics if it was not loaded.
+pub fn get() -> &'static Config {
+    CONFIG
+        .get()
+        .expect("the configuration should be loaded with `config::init` first")
+}
+
+impl Config {
+    /// Read `aoc.toml` from the current directory and apply the environment variable overrides.
+    pub fn load() -> Result<Self, ConfigError> {
+        let toml = match fs::read_to_string(CONFIG_FILE_NAME) {
+            Ok(toml) => toml,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(ConfigError::IO(e)),
+        };
+
+        Self::from_sources(&toml, |key| env::var(key).ok()).map_err(ConfigError::Invalid)
+    }
+
+    fn from_sources(toml: &str, env: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let mut config = Config::default();
+
+        for (key, value) in config_file::read_settings(toml)? {
+            config
+                .set(&key, &Value::Toml(value))
+                .map_err(|e| format!("{CONFIG_FILE_NAME}: `{key}`: {e}"))?;
+        }
+
+        for (var, key) in ENV_OVERRIDES {
+            if let Some(value) = env(var) {
+                config
+                    .set(key, &Value::Env(value))
+                    .map_err(|e| format!("{var}: {e}"))?;
+            }
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn set(&mut self, key: &str, value: &Value) -> Result<(), String> {
+        match key {
+            "year" => {
+                let year = u16::try_from(value.integer()?).ok().and_then(Year::new);
+                self.year = Some(year.ok_or("expected a year of advent of code, 2015 or later.")?);
+            }
+            "data_dir" => self.data_dir = value.string()?,
+            "readme.path" => self.readme.path = value.string()?,
+            "readme.benchmarks_marker" => self.readme.benchmarks_marker = value.string()?,
+            "readme.stars_marker" => self.readme.stars_marker = value.string()?,
+            "bench.target_ms" => self.bench.target = Duration::from_millis(value.count()?),
+            "bench.min_samples" => self.bench.min_samples = value.count()?.into(),
+            "bench.max_samples" => self.bench.max_samples = value.count()?.into(),
+            "bench.warmup" => self.bench.warmup = Some(value.count()?.into()),
+            "backend" => {
+                self.backend = match value.string()?.as_str() {
+                    "native" => Backend::Native,
+                    "aoc-cli" => Backend::AocCli,
+                    other => {
+                        return Err(format!(
+                            "unknown backend `{other}`, expected `native` or `aoc-cli`."
+                        ))
+                    }
+                }
+            }
+            "submit.check_ledger" => self.submit.check_ledger = value.boolean()?,
+            "submit.update_readme" => self.submit.update_readme = value.boolean()?,
+            "run.timeout_secs" => {
+                let secs = value.count()?;
+                self.run.timeout = (secs > 0).then(|| Duration::from_secs(secs));
+            }
+            _ => return Err("unknown setting.".into()),
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.data_dir.is_empty() {
+            return Err("`data_dir` must not be empty.".into());
+        }
+        if self.readme.benchmarks_marker.is_empty() || self.readme.stars_marker.is_empty() {
+            return Err("readme markers must not be empty.".into());
+        }
+        if self.readme.benchmarks_marker == self.readme.stars_marker {
+            return Err("the benchmarks and stars tables need different readme markers.".into());
+        }
+        self.bench
+            .validate()
+            .map_err(|e| format!("bench configuration: {e}"))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A value from the configuration file, or the raw value of an environment variable.
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Toml(toml::Value),
+    Env(String),
+}
+
+impl Value {
+    fn string(&self) -> Result<String, String> {
+        match self {
+            Value::Toml(toml::Value::String(s)) | Value::Env(s) => Ok(s.clone()),
+            _ => Err("expected a string.".into()),
+        }
+    }
+
+    fn integer(&self) -> Result<i64, String> {
+        match self {
+            Value::Toml(toml::Value::Integer(n)) => Ok(*n),
+            Value::Env(s) => s
+                .trim()
+                .parse()
+                .map_err(|_| format!("expected an integer, got `{s}`.")),
+            _ => Err("expected an integer.".into()),
+        }
+    }
+
+    /// A non-negative integer, e.g. a number of samples.
+    fn count(&self) -> Result<u64, String> {
+        let n = self.integer()?;
+        u64::try_from(n).map_err(|_| format!("expected a positive integer, got `{n}`."))
+    }
+
+    fn boolean(&self) -> Result<bool, String> {
+        match self {
+            Value::Toml(toml::Value::Boolean(b)) => Ok(*b),
+            Value::Env(s) => match s.trim() {
+                "true" | "1" => Ok(true),
+                "false" | "0" => Ok(false),
+                s => Err(format!("expected `true` or `false`, got `{s}`.")),
+            },
+            _ => Err("expected `true` or `false`.".into()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Config, ReadmeConfig};
+    use crate::template::aoc_client::Backend;
+    use crate::year;
+    use std::time::Duration;
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn reads_settings() {
+        let toml = r#"
+            year = 2022 # the default year
+            data_dir = "puzzles"
+            backend = "aoc-cli"
+
+            [readme]
+            path = "docs/README.md"
+            stars_marker = "<!-- # stars -->"
+
+            [bench]
+            target_ms = 2_000
+            max_samples = 500
+
+            [submit]
+            update_readme = false
+
+            [run]
+            timeout_secs = 30
+        "#;
+
+        let config = Config::from_sources(toml, no_env).unwrap();
+        let default = Config::default();
+
+        assert_eq!(config.year, Some(year!(2022)));
+        assert_eq!(config.data_dir, "puzzles");
+        assert_eq!(
+            config.readme,
+            ReadmeConfig {
+                path: "docs/README.md".into(),
+                stars_marker: "<!-- # stars -->".into(),
+                ..default.readme
+            }
+        );
+        assert_eq!(config.bench.target, Duration::from_secs(2));
+        assert_eq!(config.bench.max_samples, 500);
+        assert_eq!(config.bench.min_samples, default.bench.min_samples);
+        assert_eq!(config.backend, Backend::AocCli);
+        assert!(config.submit.check_ledger);
+        assert!(!config.submit.update_readme);
+        assert_eq!(config.run.timeout, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn reads_any_toml_syntax() {
+        let toml = "data_dir = 'puzzles'\nbench = { warmup = 3 }\n\"submit\".check_ledger = false";
+
+        let config = Config::from_sources(toml, no_env).unwrap();
+        assert_eq!(config.data_dir, "puzzles");
+        assert_eq!(config.bench.warmup, Some(3));
+        assert!(!config.submit.check_ledger);
+    }
+
+    #[test]
+    fn overrides_settings_from_env() {
+        let env = |key: &str| match key {
+            "AOC_YEAR" => Some("2021".into()),
+            "AOC_BENCH_WARMUP" => Some("5".into()),
+            "AOC_TIMEOUT_SECS" => Some("0".into()),
+            _ => None,
+        };
+
+        let toml = "year = 2023\n[bench]\nwarmup = 50\n[run]\ntimeout_secs = 10";
+        let config = Config::from_sources(toml, env).unwrap();
+        assert_eq!(config.year, Some(year!(2021)));
+        assert_eq!(config.bench.warmup, Some(5));
+        assert_eq!(config.run.timeout, None);
+    }
+
+    #[test]
+    fn reports_invalid_settings() {
+        let error = |toml: &str| Config::from_sources(toml, no_env).unwrap_err();
+
+        assert_eq!(
+            error("\nyear = 23"),
+            "aoc.toml: `year`: expected a year of advent of code, 2015 or later."
+        );
+        assert_eq!(
+            error("[bench]\nmin_samples = \"ten\""),
+            "aoc.toml: `bench.min_samples`: expected an integer."
+        );
+        assert_eq!(
+            error("colour = true"),
+            "aoc.toml: `colour`: unknown setting."
+        );
+        assert!(error("[bench").starts_with("aoc.toml: TOML parse error at line 1"));
+        assert_eq!(
+            error("[bench]\nmin_samples = 20\nmax_samples = 10"),
+            "bench configuration: the minimum number of samples (20) exceeds the maximum (10)."
+        );
+
+        let env = |key: &str| (key == "AOC_YEAR").then(|| "next year".into());
+        assert_eq!(
+            Config::from_sources("", env).unwrap_err(),
+            "AOC_YEAR: expected an integer, got `next year`."
+        );
+    }
+}
@@ -0,0 +1,26 @@
+/// Reader of the settings in `aoc.toml`, shared by the configuration and `build.rs`, which includes this file as a module.
+/// It must therefore not depend on the rest of the template.
+use toml::{Table, Value};
+
+pub static CONFIG_FILE_NAME: &str = "aoc.toml";
+
+/// Parse a TOML file into its settings, where the keys of nested tables are joined by dots, e.g. `bench.target_ms`.
+pub fn read_settings(s: &str) -> Result<Vec<(String, Value)>, String> {
+    let table: Table = s
+        .parse()
+        .map_err(|e: toml::de::Error| format!("{CONFIG_FILE_NAME}: {e}"))?;
+
+    let mut settings = vec![];
+    flatten("", table, &mut settings);
+    Ok(settings)
+}
+
+fn flatten(prefix: &str, table: Table, settings: &mut Vec<(String, Value)>) {
+    for (key, value) in table {
+        let key = format!("{prefix}{key}");
+        match value {
+            Value::Table(table) => flatten(&format!("{key}."), table, settings),
+            value => settings.push((key, value)),
+        }
+    }
+}
@@ -0,0 +1,239 @@
+/// Extraction of example inputs and their expected answers from puzzle descriptions.
+/// An example is a code block introduced by a paragraph that mentions an example and ends with a colon, e.g. `For example:`.
+/// Its expected answer is the last emphasized inline code (`<code><em>42</em></code>`) that follows the block in the same part.
+/// Part two usually reuses the first example of part one, so its answers are attributed to that example unless part two introduces one of its own.
+///
+/// The expected answers are written to an answers file next to the examples, which is read back by [`parse_answers`].
+use std::mem;
+
+/// An example input with the expected answers stated in the description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    /// Pairs of part and expected answer.
+    pub answers: Vec<(u8, String)>,
+}
+
+/// A line of an answers file, e.g. `01-2.txt 2 281`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExampleAnswer {
+    pub file: String,
+    pub part: u8,
+    pub expected: String,
+}
+
+/// Tracks which example the text that is being read refers to.
+#[derive(Default)]
+struct State {
+    examples: Vec<Example>,
+    part: u8,
+    current: Option<usize>,
+    answer: Option<String>,
+}
+
+impl State {
+    /// Attribute the last answer found to the current example.
+    fn flush(&mut self) {
+        if let (Some(index), Some(answer)) = (self.current, self.answer.take()) {
+            self.examples[index].answers.push((self.part, answer));
+        }
+    }
+
+    fn start_example(&mut self, input: String) {
+        self.flush();
+
+        let index = match self.examples.iter().position(|e| e.input == input) {
+            Some(index) => index,
+            None => {
+                self.examples.push(Example {
+                    input,
+                    answers: vec![],
+                });
+                self.examples.len() - 1
+            }
+        };
+
+        self.current = Some(index);
+    }
+
+    fn start_part_two(&mut self) {
+        self.flush();
+        self.part = 2;
+        self.current = (!self.examples.is_empty()).then_some(0);
+    }
+
+    fn read_text(&mut self, text: &str) {
+        if self.current.is_some() {
+            if let Some(answer) = emphasized_code(text).last() {
+                self.answer = Some((*answer).to_string());
+            }
+        }
+    }
+}
+
+/// Find all examples in puzzle markdown, in order of appearance.
+pub fn extract(markdown: &str) -> Vec<Example> {
+    let mut state = State {
+        part: 1,
+        ..State::default()
+    };
+
+    let mut paragraph = String::new();
+    let mut intro = String::new();
+    let mut code_block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if let Some(block) = &mut code_block {
+            if line.starts_with("```") {
+                if is_example_intro(&intro) {
+                    state.start_example(block.clone());
+                }
+                intro.clear();
+                code_block = None;
+            } else {
+                block.push_str(line);
+                block.push('\n');
+            }
+        } else if line.starts_with("```") {
+            if !paragraph.is_empty() {
+                intro = mem::take(&mut paragraph);
+            }
+            code_block = Some(String::new());
+        } else if line.starts_with('#') {
+            paragraph.clear();
+            if line.contains("Part Two") {
+                state.start_part_two();
+            }
+        } else if line.trim().is_empty() {
+            if !paragraph.is_empty() {
+                intro = mem::take(&mut paragraph);
+            }
+        } else {
+            state.read_text(line);
+            paragraph.push_str(line);
+            paragraph.push(' ');
+        }
+    }
+
+    state.flush();
+    state.examples
+}
+
+/// Read the lines of an answers file in the format `<example file> <part> <expected answer>`.
+/// Comments and malformed lines are skipped, `build.rs` reports the latter when generating the example tests.
+pub fn parse_answers(s: &str) -> Vec<ExampleAnswer> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, char::is_whitespace);
+            let file = fields.next()?;
+            let part = fields
+                .next()?
+                .parse()
+                .ok()
+                .filter(|part| matches!(part, 1 | 2))?;
+            let expected = fields.next()?.trim();
+
+            (!expected.is_empty()).then(|| ExampleAnswer {
+                file: file.into(),
+                part,
+                expected: expected.into(),
+            })
+        })
+        .collect()
+}
+
+fn is_example_intro(paragraph: &str) -> bool {
+    paragraph.trim_end().ends_with(':') && paragraph.to_lowercase().contains("example")
+}
+
+/// The contents of all emphasized inline code, e.g. `142` in ``*`142`*``.
+fn emphasized_code(text: &str) -> Vec<&str> {
+    text.split("*`")
+        .skip(1)
+        .filter_map(|s| s.split_once("`*").map(|(code, _)| code))
+        .filter(|code| !code.is_empty() && !code.contains(char::is_whitespace))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, parse_answers, Example, ExampleAnswer};
+
+    #[test]
+    fn extracts_examples() {
+        let markdown = "## --- Day 1: Trebuchet?! ---
+
+Here is an *`important`* note.
+
+For example:
+
+```
+1abc2
+a1b2c3d4e5f
+```
+
+In this example, the values are `12` and *`15`*. Adding these together produces *`27`*.
+
+```
+not an example
+```
+
+## --- Part Two ---
+
+Using the same example, the sum is *`30`*.
+
+Here is another example:
+
+```
+two1nine
+```
+
+Adding these together produces *`29`*.
+";
+
+        assert_eq!(
+            extract(markdown),
+            vec![
+                Example {
+                    input: "1abc2\na1b2c3d4e5f\n".into(),
+                    answers: vec![(1, "27".into()), (2, "30".into())],
+                },
+                Example {
+                    input: "two1nine\n".into(),
+                    answers: vec![(2, "29".into())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_descriptions_without_examples() {
+        assert!(extract("## --- Day 1 ---\n\nNo *`examples`* here.\n").is_empty());
+    }
+
+    #[test]
+    fn parses_answers() {
+        let answers = parse_answers(
+            "# file part answer\n01.txt 1 142\n\n01-2.txt 2 281\n01.txt 3 0\n01.txt 2\n",
+        );
+        assert_eq!(
+            answers,
+            vec![
+                ExampleAnswer {
+                    file: "01.txt".into(),
+                    part: 1,
+                    expected: "142".into()
+                },
+                ExampleAnswer {
+                    file: "01-2.txt".into(),
+                    part: 2,
+                    expected: "281".into()
+                },
+            ]
+        );
+    }
+}
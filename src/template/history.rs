@@ -0,0 +1,297 @@
+/// History of `cargo time` runs, used to detect performance regressions.
+/// Each run is appended as one JSON line to the history file, so earlier runs are never overwritten.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::config::ConfigError;
+use crate::template::timings::Timing;
+use crate::template::{unix_timestamp, Day, Year};
+
+static HISTORY_FILE_NAME: &str = "timing_history.jsonl";
+
+fn get_file_path(year: Year) -> Result<String, ConfigError> {
+    Ok(format!("{}/{HISTORY_FILE_NAME}", year.data_dir()?))
+}
+
+/// A single `cargo time` run.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The short hash of the commit that was checked out, if available.
+    pub commit: Option<String>,
+    /// Optional name of the run, which allows referring to it as a baseline.
+    pub name: Option<String>,
+    pub timings: Vec<Timing>,
+}
+
+impl Run {
+    /// Describe a run of `timings` that happened just now.
+    pub fn now(name: Option<String>, timings: Vec<Timing>) -> Self {
+        Run {
+            timestamp: unix_timestamp(),
+            commit: current_commit(),
+            name,
+            timings,
+        }
+    }
+
+    pub fn timing(&self, day: Day) -> Option<&Timing> {
+        self.timings.iter().find(|t| t.day == day)
+    }
+
+    /// Human-readable description of the run, e.g. `before-refactor (a1b2c3d, 2023-12-14 08:12 UTC)`.
+    pub fn describe(&self) -> String {
+        let time = format_timestamp(self.timestamp);
+        let details = match &self.commit {
+            Some(commit) => format!("{commit}, {time}"),
+            None => time,
+        };
+
+        match &self.name {
+            Some(name) => format!("{name} ({details})"),
+            None => details,
+        }
+    }
+
+    /// Whether `baseline` refers to this run by its name or a prefix of its commit hash.
+    pub fn matches(&self, baseline: &str) -> bool {
+        self.name.as_deref() == Some(baseline)
+            || self
+                .commit
+                .as_deref()
+                .is_some_and(|commit| commit.starts_with(baseline))
+    }
+}
+
+/// Append a run to the history file of `year`.
+pub fn append(year: Year, run: &Run) -> Result<(), io::Error> {
+    let line = JsonValue::from(run)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_file_path(year)?)?;
+
+    writeln!(file, "{line}")
+}
+
+/// Read all runs from the history file of `year`, oldest first. If not present, returns no runs.
+pub fn read_from_file(year: Year) -> Result<Vec<Run>, String> {
+    let Ok(s) = fs::read_to_string(get_file_path(year).map_err(|e| e.to_string())?) else {
+        return Ok(vec![]);
+    };
+
+    parse_lines(&s)
+}
+
+fn parse_lines(s: &str) -> Result<Vec<Run>, String> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json = JsonValue::from_str(line).or(Err("History entry is not valid JSON."))?;
+            Run::try_from(&json)
+        })
+        .collect()
+}
+
+/// Find the run to compare `day` against: the named `baseline`, or the latest run that timed `day`.
+pub fn find_baseline<'a>(runs: &'a [Run], day: Day, baseline: Option<&str>) -> Option<&'a Run> {
+    match baseline {
+        Some(baseline) => runs.iter().rev().find(|run| run.matches(baseline)),
+        None => runs.iter().rev().find(|run| run.timing(day).is_some()),
+    }
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+/// Format a unix timestamp as UTC date and time.
+fn format_timestamp(timestamp: u64) -> String {
+    let (days, seconds) = (timestamp / 86_400, timestamp % 86_400);
+
+    // converts days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02} UTC",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Run> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "name".into(),
+            match &value.name {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "timings".into(),
+            JsonValue::Array(value.timings.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|timestamp| *timestamp as u64)
+            .ok_or("Expected history.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected history.commit to be null or string.")?;
+
+        let name = json
+            .get("name")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected history.name to be null or string.")?;
+
+        let timings = json
+            .get("timings")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected history.timings to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Run {
+            timestamp,
+            commit: commit.cloned(),
+            name: name.cloned(),
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_baseline, format_timestamp, parse_lines, Run};
+    use crate::day;
+    use crate::template::{timings::Timing, Day};
+    use tinyjson::JsonValue;
+
+    fn run(timestamp: u64, commit: &str, name: Option<&str>, days: &[u8]) -> Run {
+        Run {
+            timestamp,
+            commit: Some(commit.into()),
+            name: name.map(String::from),
+            timings: days
+                .iter()
+                .map(|day| Timing::from_records(Day::new(*day).unwrap(), &[]))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn roundtrips_runs() {
+        let runs = [
+            run(1, "a1b2c3d", Some("base"), &[1, 2]),
+            run(2, "e4f5", None, &[]),
+        ];
+        let lines = runs
+            .iter()
+            .map(|run| JsonValue::from(run).stringify().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let parsed = parse_lines(&lines).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].name, Some("base".into()));
+        assert_eq!(parsed[0].timings.len(), 2);
+        assert_eq!(parsed[1].commit, Some("e4f5".into()));
+    }
+
+    #[test]
+    fn finds_baselines() {
+        let runs = [
+            run(1, "a1b2c3d", Some("base"), &[1, 2]),
+            run(2, "e4f5a6b", None, &[2]),
+            run(3, "c7d8e9f", None, &[3]),
+        ];
+
+        let previous = find_baseline(&runs, day!(2), None).unwrap();
+        assert_eq!(previous.timestamp, 2);
+        assert_eq!(find_baseline(&runs, day!(1), None).unwrap().timestamp, 1);
+        assert_eq!(
+            find_baseline(&runs, day!(3), Some("base"))
+                .unwrap()
+                .timestamp,
+            1
+        );
+        assert_eq!(
+            find_baseline(&runs, day!(3), Some("c7d8"))
+                .unwrap()
+                .timestamp,
+            3
+        );
+        assert!(find_baseline(&runs, day!(4), None).is_none());
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(1_702_541_520), "2023-12-14 08:12 UTC");
+    }
+}
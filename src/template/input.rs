@@ -0,0 +1,185 @@
+/// Selects the input a solution binary runs against, see `--input` and `--example` in the readme.
+use std::{
+    fs,
+    io::{self, Read},
+    ops::Range,
+};
+
+use crate::template::config::ConfigError;
+use crate::template::Puzzle;
+
+/// The range of arguments that `--example [k]` spans, and the example it selects.
+pub type ExampleArgs = (Range<usize>, Option<u8>);
+
+/// Where a solution reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input of the day, e.g. `data/2023/inputs/01.txt`.
+    #[default]
+    Puzzle,
+    /// An example of the day, e.g. `data/2023/examples/01.txt`, or `01-2.txt` for example `2`.
+    Example(Option<u8>),
+    /// Any file, e.g. a generated stress input.
+    File(String),
+    Stdin,
+}
+
+impl InputSource {
+    /// Combine the values of `--input <path>` and `--example [k]`, where `-` as path reads stdin.
+    pub fn new(input: Option<String>, example: Option<Option<u8>>) -> Result<Self, String> {
+        match (input, example) {
+            (Some(_), Some(_)) => Err("pass either `--input` or `--example`, not both.".into()),
+            (Some(path), None) if path == "-" => Ok(InputSource::Stdin),
+            (Some(path), None) => Ok(InputSource::File(path)),
+            (None, Some(k)) => Ok(InputSource::Example(k)),
+            (None, None) => Ok(InputSource::Puzzle),
+        }
+    }
+
+    /// Parse `--input <path>` and `--example [k]` from the arguments of a solution binary.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let input = match args.iter().position(|arg| arg == "--input") {
+            Some(index) => match args.get(index + 1).filter(|arg| !arg.starts_with("--")) {
+                Some(path) => Some(path.clone()),
+                None => return Err("expected a path or `-` after `--input`.".into()),
+            },
+            None => None,
+        };
+
+        let example = Self::find_example(args)?.map(|(_, example)| example);
+
+        Self::new(input, example)
+    }
+
+    /// Find `--example`, followed by the number `k` of an example if one is given, as `--example k` or `--example=k`.
+    pub fn find_example(args: &[String]) -> Result<Option<ExampleArgs>, String> {
+        let Some(index) = args
+            .iter()
+            .position(|arg| arg == "--example" || arg.starts_with("--example="))
+        else {
+            return Ok(None);
+        };
+
+        let (k, end) = match args[index].strip_prefix("--example=") {
+            Some(k) => (Some(k), index + 1),
+            None => match args.get(index + 1) {
+                Some(k) if !k.is_empty() && k.bytes().all(|b| b.is_ascii_digit()) => {
+                    (Some(k.as_str()), index + 2)
+                }
+                _ => (None, index + 1),
+            },
+        };
+
+        let example = k
+            .map(|k| {
+                k.parse()
+                    .map_err(|_| format!("expected the number of an example, got `{k}`."))
+            })
+            .transpose()?;
+
+        Ok(Some((index..end, example)))
+    }
+
+    /// The arguments that select this source, to pass them on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(k)) => vec!["--example".into(), k.to_string()],
+            InputSource::File(path) => vec!["--input".into(), path.clone()],
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// The path of the file to read, `None` for stdin.
+    pub fn path(&self, puzzle: Puzzle) -> Result<Option<String>, ConfigError> {
+        Ok(match self {
+            InputSource::Puzzle => Some(puzzle.input_path()?),
+            InputSource::Example(None) => {
+                Some(puzzle.example_path(&format!("{}.txt", puzzle.day))?)
+            }
+            InputSource::Example(Some(k)) => {
+                Some(puzzle.example_path(&format!("{}-{k}.txt", puzzle.day))?)
+            }
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        })
+    }
+
+    pub fn read(&self, puzzle: Puzzle) -> Result<String, String> {
+        match self.path(puzzle).map_err(|e| e.to_string())? {
+            Some(path) => fs::read_to_string(&path)
+                .map_err(|e| format!("could not open input file \"{path}\": {e}")),
+            None => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("could not read input from stdin: {e}"))?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Describes the source in the output of a solution, e.g. `example data/2023/examples/01-2.txt`.
+    pub fn describe(&self, puzzle: Puzzle) -> Result<String, ConfigError> {
+        Ok(match (self, self.path(puzzle)?) {
+            (InputSource::Example(_), Some(path)) => format!("example {path}"),
+            (_, Some(path)) => path,
+            (_, None) => "stdin".into(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+
+    fn parse(args: &[&str]) -> Result<InputSource, String> {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        InputSource::from_args(&args)
+    }
+
+    #[test]
+    fn parses_args() {
+        assert_eq!(parse(&["2023-01", "--time"]), Ok(InputSource::Puzzle));
+        assert_eq!(
+            parse(&["2023-01", "--input", "stress.txt"]),
+            Ok(InputSource::File("stress.txt".into()))
+        );
+        assert_eq!(parse(&["2023-01", "--input", "-"]), Ok(InputSource::Stdin));
+        assert_eq!(
+            parse(&["2023-01", "--example", "--time"]),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            parse(&["2023-01", "--example=2"]),
+            Ok(InputSource::Example(Some(2)))
+        );
+        assert_eq!(
+            parse(&["2023-01", "--example", "2", "--time"]),
+            Ok(InputSource::Example(Some(2)))
+        );
+
+        assert!(parse(&["2023-01", "--input"]).is_err());
+        assert!(parse(&["2023-01", "--example=two"]).is_err());
+        assert!(parse(&["2023-01", "--example", "300"]).is_err());
+        assert!(parse(&["2023-01", "--example", "--input", "a.txt"]).is_err());
+    }
+
+    #[test]
+    fn round_trips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example(None),
+            InputSource::Example(Some(3)),
+            InputSource::File("inputs/alice.txt".into()),
+            InputSource::Stdin,
+        ] {
+            let mut args = vec!["2023-01".to_string()];
+            args.extend(source.to_args());
+            assert_eq!(InputSource::from_args(&args), Ok(source));
+        }
+    }
+}
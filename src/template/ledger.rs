@@ -0,0 +1,349 @@
+/// Ledger of all answers submitted to the Advent of Code website and their verdicts.
+/// Submissions are appended as one JSON line each, and consulted to avoid submitting answers that are known to be wrong.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    str::FromStr,
+    time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{Hint, Verdict};
+use crate::template::config::ConfigError;
+use crate::template::{unix_timestamp, Day, Year};
+
+static LEDGER_FILE_NAME: &str = "submissions.jsonl";
+
+fn get_file_path(year: Year) -> Result<String, ConfigError> {
+    Ok(format!("{}/{LEDGER_FILE_NAME}", year.data_dir()?))
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Entry {
+    /// Describe a submission that happened just now.
+    pub fn now(day: Day, part: u8, answer: &str, verdict: Verdict) -> Self {
+        Entry {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp: unix_timestamp(),
+        }
+    }
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    AlreadyTried(Verdict),
+    /// The answer is not lower than an answer that was too high.
+    AboveBound(String),
+    /// The answer is not higher than an answer that was too low.
+    BelowBound(String),
+    Cooldown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::AlreadyTried(verdict) => write!(
+                f,
+                "this answer was already submitted and was {}.",
+                verdict_description(*verdict)
+            ),
+            Refusal::AboveBound(bound) => {
+                write!(f, "{bound} was too high, so this answer is too.")
+            }
+            Refusal::BelowBound(bound) => write!(f, "{bound} was too low, so this answer is too."),
+            Refusal::Cooldown(wait) => write!(
+                f,
+                "the server asked to wait before submitting again, {}s left.",
+                wait.as_secs()
+            ),
+        }
+    }
+}
+
+fn verdict_description(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => "correct",
+        Verdict::Incorrect(Some(Hint::TooHigh)) => "too high",
+        Verdict::Incorrect(Some(Hint::TooLow)) => "too low",
+        Verdict::Incorrect(None) => "wrong",
+        Verdict::TooRecent(_) => "submitted too recently",
+        Verdict::WrongLevel => "for the wrong level",
+        Verdict::Unknown => "not understood",
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    pub entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Read the ledger of `year`. If not present, returns an empty ledger.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let Ok(s) = fs::read_to_string(get_file_path(year).map_err(|e| e.to_string())?) else {
+            return Ok(Ledger::default());
+        };
+
+        parse_lines(&s).map(|entries| Ledger { entries })
+    }
+
+    /// Append a submission to the ledger file of `year`.
+    pub fn append(year: Year, entry: &Entry) -> Result<(), io::Error> {
+        let line = JsonValue::from(entry)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(get_file_path(year)?)?;
+
+        writeln!(file, "{line}")
+    }
+
+    /// Check whether `answer` is worth submitting, given the earlier submissions for the same part.
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let entries = self
+            .entries
+            .iter()
+            .filter(|e| e.day == day && e.part == part);
+
+        let value = answer.parse::<i128>().ok();
+
+        for entry in entries {
+            match entry.verdict {
+                Verdict::Correct => return Err(Refusal::AlreadySolved(entry.answer.clone())),
+                Verdict::TooRecent(Some(wait)) => {
+                    let until = entry.timestamp + wait.as_secs();
+                    if until > now {
+                        return Err(Refusal::Cooldown(Duration::from_secs(until - now)));
+                    }
+                }
+                Verdict::Incorrect(hint) => {
+                    if entry.answer == answer {
+                        return Err(Refusal::AlreadyTried(entry.verdict));
+                    }
+
+                    let bound = entry.answer.parse::<i128>().ok();
+                    match (hint, value, bound) {
+                        (Some(Hint::TooHigh), Some(value), Some(bound)) if value >= bound => {
+                            return Err(Refusal::AboveBound(entry.answer.clone()));
+                        }
+                        (Some(Hint::TooLow), Some(value), Some(bound)) if value <= bound => {
+                            return Err(Refusal::BelowBound(entry.answer.clone()));
+                        }
+                        _ => {}
+                    }
+                }
+                Verdict::TooRecent(None) | Verdict::WrongLevel | Verdict::Unknown => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_lines(s: &str) -> Result<Vec<Entry>, String> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json = JsonValue::from_str(line).or(Err("Ledger entry is not valid JSON."))?;
+            Entry::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn verdict_to_str(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => "correct",
+        Verdict::Incorrect(Some(Hint::TooHigh)) => "too_high",
+        Verdict::Incorrect(Some(Hint::TooLow)) => "too_low",
+        Verdict::Incorrect(None) => "incorrect",
+        Verdict::TooRecent(_) => "too_recent",
+        Verdict::WrongLevel => "wrong_level",
+        Verdict::Unknown => "unknown",
+    }
+}
+
+fn verdict_from_str(s: &str, wait: Option<Duration>) -> Option<Verdict> {
+    match s {
+        "correct" => Some(Verdict::Correct),
+        "too_high" => Some(Verdict::Incorrect(Some(Hint::TooHigh))),
+        "too_low" => Some(Verdict::Incorrect(Some(Hint::TooLow))),
+        "incorrect" => Some(Verdict::Incorrect(None)),
+        "too_recent" => Some(Verdict::TooRecent(wait)),
+        "wrong_level" => Some(Verdict::WrongLevel),
+        "unknown" => Some(Verdict::Unknown),
+        _ => None,
+    }
+}
+
+impl From<&Entry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Entry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(verdict_to_str(value.verdict).into()),
+        );
+        map.insert(
+            "wait_secs".into(),
+            match value.verdict {
+                Verdict::TooRecent(Some(wait)) => JsonValue::Number(wait.as_secs() as f64),
+                _ => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Entry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected ledger entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected entry.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1.0 || **part == 2.0)
+            .map(|part| *part as u8)
+            .ok_or("Expected entry.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.answer to be a string.")?;
+
+        let wait = json
+            .get("wait_secs")
+            .and_then(|v| v.get::<f64>())
+            .map(|secs| Duration::from_secs(*secs as u64));
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|verdict| verdict_from_str(verdict, wait))
+            .ok_or("Expected entry.verdict to be a verdict.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|timestamp| *timestamp as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        Ok(Entry {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_lines, Entry, Ledger, Refusal};
+    use crate::day;
+    use crate::template::aoc_client::{Hint, Verdict};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn entry(answer: &str, verdict: Verdict, timestamp: u64) -> Entry {
+        Entry {
+            day: day!(1),
+            part: 1,
+            answer: answer.into(),
+            verdict,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = entry("42", Verdict::TooRecent(Some(Duration::from_secs(30))), 100);
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        assert_eq!(parse_lines(&line).unwrap(), vec![entry]);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let ledger = Ledger {
+            entries: vec![
+                entry("100", Verdict::Incorrect(Some(Hint::TooHigh)), 0),
+                entry("10", Verdict::Incorrect(Some(Hint::TooLow)), 0),
+                entry("50", Verdict::Incorrect(None), 0),
+            ],
+        };
+
+        let check = |answer| ledger.check(day!(1), 1, answer, 1000);
+        assert_eq!(check("120"), Err(Refusal::AboveBound("100".into())));
+        assert_eq!(check("9"), Err(Refusal::BelowBound("10".into())));
+        assert_eq!(
+            check("50"),
+            Err(Refusal::AlreadyTried(Verdict::Incorrect(None)))
+        );
+        assert_eq!(check("51"), Ok(()));
+        assert_eq!(ledger.check(day!(1), 2, "120", 1000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_during_cooldown() {
+        let ledger = Ledger {
+            entries: vec![entry(
+                "42",
+                Verdict::TooRecent(Some(Duration::from_secs(60))),
+                1000,
+            )],
+        };
+
+        assert_eq!(
+            ledger.check(day!(1), 1, "43", 1015),
+            Err(Refusal::Cooldown(Duration::from_secs(45)))
+        );
+        assert_eq!(ledger.check(day!(1), 1, "43", 1060), Ok(()));
+    }
+}
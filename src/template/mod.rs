@@ -1,68 +1,248 @@
-use std::{env, fs};
+use std::{
+    env, fs,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod config;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use input::*;
+pub use solution::*;
+pub use year::*;
 
+mod answers;
+mod artifacts;
+mod bench;
+mod budgets;
+mod config_file;
 mod day;
+mod examples;
+mod history;
+mod input;
+mod ledger;
+mod puzzle;
 mod readme_benchmarks;
+mod readme_stars;
+mod records;
 mod run_multi;
+mod solution;
+mod table;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+/// Helper function that reads a text file of a puzzle to a string, e.g. `data/2023/inputs/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: Puzzle) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(puzzle.year.data_dir().unwrap_or_else(|e| panic!("{e}")))
+        .join(folder)
+        .join(format!("{}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Helper function that reads a text file of a puzzle to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: Puzzle, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(puzzle.year.data_dir().unwrap_or_else(|e| panic!("{e}")))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Seconds since the unix epoch, as stored in the timing history and the submission ledger.
+pub(crate) fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// A `cargo` command without the variables that `cargo run` sets for the runner, e.g. `CARGO_MANIFEST_DIR` and `OUT_DIR`.
+/// Build scripts that track them would otherwise rebuild their crates whenever the runner and the command build in turn.
+pub(crate) fn cargo_command() -> Command {
+    let mut cmd = Command::new("cargo");
+
+    for (key, _) in env::vars_os() {
+        let is_run_variable = key.to_str().is_some_and(|key| {
+            matches!(
+                key,
+                "CARGO"
+                    | "OUT_DIR"
+                    | "CARGO_MANIFEST_DIR"
+                    | "CARGO_MANIFEST_PATH"
+                    | "CARGO_CRATE_NAME"
+                    | "CARGO_BIN_NAME"
+                    | "CARGO_PRIMARY_PACKAGE"
+            ) || key.starts_with("CARGO_PKG_")
+        });
+
+        if is_run_variable {
+            cmd.env_remove(key);
+        }
+    }
+
+    cmd
+}
+
+/// Runs a part of `solution` against an example file in `data/<year>/examples` and asserts that it returns `expected`.
+pub fn check_example(solution: &dyn Solution, year: Year, file: &str, part: u8, expected: &str) {
+    let cwd = env::current_dir().unwrap();
+    let puzzle = Puzzle::new(year, solution.day());
+    let filepath = cwd.join(puzzle.example_path(file).unwrap_or_else(|e| panic!("{e}")));
+    let input = fs::read_to_string(filepath).expect("could not open example file");
+
+    let parsed = solution.parse(&input);
+    let outcome = match part {
+        1 => solution.part_one(&*parsed),
+        2 => solution.part_two(&*parsed),
+        _ => panic!("expecting part 1 or 2, got {part}"),
+    };
+
+    assert_eq!(
+        outcome,
+        Outcome::Answer(expected.into()),
+        "unexpected answer for part {part} of example \"{file}\""
+    );
+}
+
+/// Creates one test per example file and part listed in `data/<year>/examples/<day>.answers`.
+///
+/// Each non-empty line of the answers file has the format `<example file> <part> <expected answer>`, e.g. `01-2.txt 2 281`.
+/// This is invoked by the [`solution!`] macro, adding an example does not require any code changes.
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        #[cfg(test)]
+        mod example_tests {
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/examples/",
+                env!("CARGO_BIN_NAME"),
+                ".rs"
+            ));
+        }
+    };
+}
+
+/// Creates the constant `DAY`, registers the solution and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Parts may return either an [`Option`] or a [`Result`], see [`PartOutput`].
+///
+/// Optionally, `parse = <fn>` names a function that prepares the input once for both parts, which then take its output by reference.
+/// `reset = <fn>` names a function that clears state kept between runs of a part, such as memoization caches.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, parse = $parse:path)? $(, reset = $reset:path)?) => {
+        $crate::solution!(@impl $day, [$($parse)?], [$($reset)?], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1 $(, parse = $parse:path)? $(, reset = $reset:path)?) => {
+        $crate::solution!(@impl $day, [$($parse)?], [$($reset)?], [part_one, 1]);
+    };
+    ($day:expr, 2 $(, parse = $parse:path)? $(, reset = $reset:path)?) => {
+        $crate::solution!(@impl $day, [$($parse)?], [$($reset)?], [part_two, 2]);
+    };
+
+    (@parse []) => {
+        $crate::template::raw_input
+    };
+    (@parse [$parse:path]) => {
+        $parse
+    };
+
+    (@has_parse []) => {
+        false
+    };
+    (@has_parse [$parse:path]) => {
+        true
+    };
+
+    (@reset) => {
+        || {}
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@reset $reset:path) => {
+        $reset
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+
+    (@main $input:ident, $puzzle:ident, [], $( [$func:ident, $part:expr] )*) => {
+        $( run_part($func, &$input, $puzzle, $part, RESET); )*
+    };
+    (@main $input:ident, $puzzle:ident, [$parse:path], $( [$func:ident, $part:expr] )*) => {
+        match run_parse($parse, &$input, $puzzle) {
+            Ok(parsed) => {
+                $( run_part($func, &parsed, $puzzle, $part, RESET); )*
+            }
+            Err(_) => {
+                $( skip_part($puzzle, $part); )*
+            }
+        }
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $parse:tt, [$($reset:path)?], $( [$func:ident, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// Clears state kept between runs of a part.
+        const RESET: fn() = $crate::solution!(@reset $($reset)?);
+
+        /// The solution for the current day, as collected by the solution registry.
+        pub struct Solver;
+
+        impl $crate::template::Solution for Solver {
+            fn day(&self) -> $crate::template::Day {
+                DAY
+            }
+
+            fn parse(&self, input: &str) -> std::boxed::Box<dyn std::any::Any> {
+                std::boxed::Box::new(($crate::solution!(@parse $parse))(input))
+            }
 
-        fn main() {
+            fn has_parse(&self) -> bool {
+                $crate::solution!(@has_parse $parse)
+            }
+
+            $(
+                fn $func(&self, input: &dyn std::any::Any) -> $crate::template::Outcome {
+                    let input = $crate::template::parsed_input($crate::solution!(@parse $parse), input);
+                    $crate::template::PartOutput::outcome(&$func(input))
+                }
+            )*
+
+            fn reset(&self) {
+                RESET();
+            }
+        }
+
+        pub fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            if let Err(e) = $crate::template::config::init() {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+            // the year is taken from the name of the binary, e.g. `2023-01`.
+            let puzzle = $crate::template::Puzzle::__for_bin(option_env!("CARGO_BIN_NAME"), DAY);
+            let input = read_input(puzzle);
+            $crate::solution!(@main input, puzzle, $parse, $( [$func, $part] )*);
         }
+
+        $crate::example_tests!();
     };
 }
@@ -0,0 +1,395 @@
+/// Conversion of puzzle descriptions from the Advent of Code website to markdown, and rendering of that markdown in the terminal.
+/// Only handles the small set of tags and markdown syntax used by puzzle descriptions.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Convert the description articles of a puzzle page to markdown.
+pub fn from_html(html: &str) -> String {
+    let mut out = String::new();
+
+    for article in articles(html) {
+        convert(article, &mut out);
+    }
+
+    let mut markdown = out.trim_end().to_string();
+    markdown.push('\n');
+    markdown
+}
+
+/// The contents of all `<article class="day-desc">` elements, one per solved part.
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article class=\"day-desc\">") {
+        rest = &rest[start..];
+        let content_start = rest.find('>').unwrap_or(0) + 1;
+        let content_end = rest.find("</article>").unwrap_or(rest.len());
+        articles.push(&rest[content_start..content_end]);
+        rest = &rest[content_end..];
+    }
+
+    articles
+}
+
+/// Extract the text of the first `<article>` of a page, e.g. the message in response to a submission.
+pub fn article_text(html: &str) -> Option<String> {
+    let start = html.find("<article")?;
+    let end = html[start..].find("</article>")? + start;
+
+    let mut text = String::new();
+    let mut rest = &html[start..end];
+
+    while !rest.is_empty() {
+        if let Some(tag) = rest.strip_prefix('<') {
+            rest = tag.find('>').map_or("", |end| &tag[end + 1..]);
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            text.push_str(&decode_entities(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+
+    Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+#[derive(Default)]
+struct State {
+    in_pre: bool,
+    /// Content of the inline code element that is being converted, and whether it is emphasized.
+    code: Option<(String, bool)>,
+    links: Vec<String>,
+}
+
+fn convert(html: &str, out: &mut String) {
+    let mut state = State::default();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(tag) = rest.strip_prefix('<') {
+            let end = tag.find('>').unwrap_or(tag.len());
+            convert_tag(&tag[..end], &mut state, out);
+            rest = tag.get(end + 1..).unwrap_or("");
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = decode_entities(&rest[..end]);
+            rest = &rest[end..];
+
+            if let Some((code, _)) = &mut state.code {
+                code.push_str(&text);
+            } else if state.in_pre
+                || !text.trim().is_empty()
+                || !(out.is_empty() || out.ends_with('\n'))
+            {
+                // whitespace between block elements is dropped.
+                out.push_str(&text);
+            }
+        }
+    }
+}
+
+fn convert_tag(tag: &str, state: &mut State, out: &mut String) {
+    let name = tag.split_whitespace().next().unwrap_or_default();
+
+    match name {
+        "h2" => out.push_str("## "),
+        "/h2" | "/p" => out.push_str("\n\n"),
+        "pre" => {
+            state.in_pre = true;
+            out.push_str("```\n");
+        }
+        "/pre" => {
+            state.in_pre = false;
+            if !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str("```\n\n");
+        }
+        "code" if !state.in_pre => state.code = Some((String::new(), false)),
+        "/code" if !state.in_pre => {
+            if let Some((code, is_emphasized)) = state.code.take() {
+                if is_emphasized {
+                    out.push_str(&format!("*`{code}`*"));
+                } else {
+                    out.push_str(&format!("`{code}`"));
+                }
+            }
+        }
+        "em" | "/em" if !state.in_pre => match &mut state.code {
+            Some((_, is_emphasized)) => *is_emphasized = true,
+            None => out.push('*'),
+        },
+        "a" => {
+            state.links.push(attribute(tag, "href").unwrap_or_default());
+            out.push('[');
+        }
+        "/a" => {
+            let href = state.links.pop().unwrap_or_default();
+            out.push_str(&format!("]({href})"));
+        }
+        "li" => out.push_str("- "),
+        "/li" => out.push('\n'),
+        "/ul" => out.push('\n'),
+        "br" | "br/" => out.push('\n'),
+        _ => {}
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')? + start;
+    Some(decode_entities(&tag[start..end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Select one part of a puzzle description. Part two starts at the `--- Part Two ---` heading.
+pub fn select_part(markdown: &str, part: u8) -> Option<&str> {
+    let part_two = markdown
+        .match_indices('#')
+        .map(|(index, _)| index)
+        .find(|index| {
+            let is_line_start = *index == 0 || markdown[..*index].ends_with('\n');
+            let line = markdown[*index..].lines().next().unwrap_or_default();
+            is_line_start && line.contains("Part Two")
+        });
+
+    match (part, part_two) {
+        (1, Some(index)) => Some(markdown[..index].trim_end()),
+        (1, None) => Some(markdown),
+        (2, Some(index)) => Some(&markdown[index..]),
+        _ => None,
+    }
+}
+
+/// Render puzzle markdown for the terminal, wrapping paragraphs and lists at `width` columns.
+/// Headings and emphasized text are bold, inline code is italic and code blocks are indented.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<&str> = vec![];
+    let mut in_code_block = false;
+
+    let flush = |paragraph: &mut Vec<&str>, out: &mut String| {
+        if let Some(first) = paragraph.first() {
+            let (prefix, indent) = if first.starts_with("- ") || first.starts_with("* ") {
+                ("- ", "  ")
+            } else {
+                ("", "")
+            };
+            let text = paragraph.join(" ");
+            let text = text.strip_prefix(prefix).unwrap_or(&text);
+            wrap(&render_inline(text), width, prefix, indent, out);
+            paragraph.clear();
+        }
+    };
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            flush(&mut paragraph, &mut out);
+            in_code_block = !in_code_block;
+            if !in_code_block {
+                out.push('\n');
+            }
+        } else if in_code_block {
+            out.push_str(&format!("    {line}\n"));
+        } else if line.starts_with('#') {
+            flush(&mut paragraph, &mut out);
+            let heading = render_inline(line.trim_start_matches('#').trim());
+            out.push_str(&format!("{ANSI_BOLD}{heading}{ANSI_RESET}\n"));
+        } else if line.trim().is_empty() {
+            flush(&mut paragraph, &mut out);
+            if !(out.is_empty() || out.ends_with("\n\n")) {
+                out.push('\n');
+            }
+        } else {
+            if line.starts_with("- ") || line.starts_with("* ") {
+                flush(&mut paragraph, &mut out);
+            }
+            paragraph.push(line.trim());
+        }
+    }
+
+    flush(&mut paragraph, &mut out);
+
+    let mut rendered = out.trim_end().to_string();
+    rendered.push('\n');
+    rendered
+}
+
+/// Replace markdown emphasis, inline code, links and escapes with ANSI styles.
+fn render_inline(text: &str) -> String {
+    let mut out = String::new();
+    let (mut is_bold, mut is_code) = (false, false);
+    let mut links = 0;
+    let mut chars = text.char_indices().peekable();
+
+    // styles are turned off by resetting all of them, then re-applying the ones still active.
+    let restyle = |out: &mut String, is_bold: bool, is_code: bool, is_on: bool| {
+        if !is_on {
+            out.push_str(ANSI_RESET);
+        }
+        if is_bold {
+            out.push_str(ANSI_BOLD);
+        }
+        if is_code {
+            out.push_str(ANSI_ITALIC);
+        }
+    };
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '`' => {
+                is_code = !is_code;
+                restyle(&mut out, is_bold && !is_code, is_code, is_code);
+            }
+            _ if is_code => out.push(c),
+            '\\' => out.extend(chars.next().map(|(_, c)| c)),
+            '*' if is_bold || chars.peek().is_some_and(|(_, c)| !c.is_whitespace()) => {
+                is_bold = !is_bold;
+                restyle(&mut out, is_bold, false, is_bold);
+            }
+            '[' if text[index..].contains("](") => links += 1,
+            ']' if links > 0 && text[index..].starts_with("](") => {
+                links -= 1;
+                for (_, c) in chars.by_ref() {
+                    if c == ')' {
+                        break;
+                    }
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    if is_bold || is_code {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+/// Number of visible characters in a string, not counting ANSI escape sequences.
+fn visible_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+
+    for c in s.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if !in_escape => width += 1,
+            _ => {}
+        }
+    }
+
+    width
+}
+
+fn wrap(text: &str, width: usize, prefix: &str, indent: &str, out: &mut String) {
+    let mut line = prefix.to_string();
+    let mut line_width = prefix.len();
+
+    for word in text.split(' ').filter(|word| visible_width(word) > 0) {
+        let word_width = visible_width(word);
+
+        if line_width > indent.len().max(prefix.len()) && line_width + 1 + word_width > width {
+            out.push_str(&line);
+            out.push('\n');
+            line = indent.to_string();
+            line_width = indent.len();
+        } else if line_width > indent.len().max(prefix.len()) {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        line.push_str(word);
+        line_width += word_width;
+    }
+
+    out.push_str(&line);
+    out.push('\n');
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{article_text, from_html, render, select_part};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn converts_descriptions() {
+        let html = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with <a href="/2023/about">global snow production</a>.</p>
+<pre><code>1abc2
+a1b2c3d4e5f
+</code></pre>
+<ul>
+<li>In this example, the sum is <code><em>142</em></code>.</li>
+<li>Compare <code>x &lt; y</code>.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>54561</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Done.</p>
+</article>
+</main>"#;
+
+        let expected = "## --- Day 1: Trebuchet?! ---
+
+Something is *wrong* with [global snow production](/2023/about).
+
+```
+1abc2
+a1b2c3d4e5f
+```
+
+- In this example, the sum is *`142`*.
+- Compare `x < y`.
+
+## --- Part Two ---
+
+Done.
+";
+
+        assert_eq!(from_html(html), expected);
+    }
+
+    #[test]
+    fn extracts_article_text() {
+        let html = "<main><article><p>That's the <em>right</em> answer!\n  You are <span>one gold star</span> closer.</p></article></main>";
+        assert_eq!(
+            article_text(html),
+            Some("That's the right answer! You are one gold star closer.".into())
+        );
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = "## --- Day 1 ---\n\nSomething is *wrong* with [global snow](/2023/about) production.\n\n```\n1abc2\n```\n\n- The sum is *`142`*.\n";
+
+        let expected = format!(
+            "{ANSI_BOLD}--- Day 1 ---{ANSI_RESET}\n\nSomething is {ANSI_BOLD}wrong{ANSI_RESET}\nwith global snow\nproduction.\n\n    1abc2\n\n- The sum is {ANSI_BOLD}{ANSI_ITALIC}142{ANSI_RESET}{ANSI_BOLD}{ANSI_RESET}.\n"
+        );
+
+        assert_eq!(render(markdown, 20), expected);
+    }
+
+    #[test]
+    fn selects_parts() {
+        let markdown = "## --- Day 1 ---\n\nOne.\n\n## --- Part Two ---\n\nTwo.\n";
+        assert_eq!(select_part(markdown, 1), Some("## --- Day 1 ---\n\nOne."));
+        assert_eq!(
+            select_part(markdown, 2),
+            Some("## --- Part Two ---\n\nTwo.\n")
+        );
+        assert_eq!(select_part("## --- Day 1 ---\n", 2), None);
+    }
+}
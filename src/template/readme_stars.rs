@@ -0,0 +1,172 @@
+/// Module that updates the readme with the stars earned so far.
+/// Produces the same table as `advent-readme-stars`, from the stars already in the table and the accepted answers.
+use std::{collections::BTreeMap, fs};
+
+use crate::template::answers::Answers;
+use crate::template::config;
+use crate::template::readme_benchmarks::Error;
+use crate::template::{Day, Year};
+
+/// The default marker around the results table, as used by `advent-readme-stars`.
+pub static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Stars earned per day, for part one and part two.
+type Stars = BTreeMap<Day, [bool; 2]>;
+
+/// The result of updating the table.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TableUpdate {
+    Updated,
+    /// The table tracks another year, so it was left unchanged.
+    Kept(Year),
+}
+
+fn locate_table(readme: &str, marker: &str) -> Result<(usize, usize), Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
+
+    let [start, end] = matches[..] else {
+        return Err(Error::Parser(
+            "Expected README to contain the stars table marker exactly twice.".into(),
+        ));
+    };
+
+    Ok((start.0, end.0 + end.1.len()))
+}
+
+/// Read the stars from the rows of an existing table, e.g. `| [Day 1](...) | ⭐ | ⭐ |`.
+fn parse_stars(table: &str) -> Stars {
+    table
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+            let [_, day, part_1, part_2, ..] = cells[..] else {
+                return None;
+            };
+
+            let day = day.strip_prefix("[Day ")?.split(']').next()?.parse().ok()?;
+            Some((day, [part_1.contains('⭐'), part_2.contains('⭐')]))
+        })
+        .collect()
+}
+
+/// Read the year from the heading of an existing table, e.g. `## 2023 Results`.
+fn parse_year(table: &str) -> Option<Year> {
+    table.lines().find_map(|line| {
+        line.strip_prefix("## ")?
+            .strip_suffix(" Results")?
+            .parse()
+            .ok()
+    })
+}
+
+fn add_answers(stars: &mut Stars, answers: &Answers) {
+    for answer in &answers.data {
+        let entry = stars.entry(answer.day).or_default();
+        entry[0] |= answer.part_1.is_some();
+        entry[1] |= answer.part_2.is_some();
+    }
+}
+
+fn construct_table(marker: &str, year: Year, stars: &Stars) -> String {
+    let mut lines: Vec<String> = vec![
+        marker.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    let star = |earned: bool| if earned { "⭐" } else { " " };
+
+    for (day, [part_1, part_2]) in stars {
+        let day = day.into_inner();
+        lines.push(format!(
+            "| [Day {day}](https://adventofcode.com/{year}/day/{day}) | {} | {} |",
+            star(*part_1),
+            star(*part_2)
+        ));
+    }
+
+    lines.push(marker.into());
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    marker: &str,
+    year: Year,
+    answers: &Answers,
+) -> Result<TableUpdate, Error> {
+    let (start, end) = locate_table(s, marker)?;
+
+    // the table tracks a single year, keep it when answers for another year are accepted.
+    if let Some(table_year) = parse_year(&s[start..end]).filter(|table_year| *table_year != year) {
+        return Ok(TableUpdate::Kept(table_year));
+    }
+
+    let mut stars = parse_stars(&s[start..end]);
+    add_answers(&mut stars, answers);
+    s.replace_range(start..end, &construct_table(marker, year, &stars));
+    Ok(TableUpdate::Updated)
+}
+
+/// Add a star for every accepted answer of `year` to the README.
+pub fn update(year: Year, answers: &Answers) -> Result<TableUpdate, Error> {
+    let config = &config::get().readme;
+    let path = &config.path;
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let update = update_content(&mut readme, &config.stars_marker, year, answers)?;
+    if update == TableUpdate::Updated {
+        fs::write(path, &readme)?;
+    }
+    Ok(update)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, TableUpdate, MARKER};
+    use crate::template::answers::Answers;
+    use crate::{day, year};
+
+    #[test]
+    fn adds_stars_for_answers() {
+        let mut s = format!(
+            "foo\n{MARKER}\n## 2023 Results\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |\n{MARKER}\nbar"
+        );
+
+        let mut answers = Answers::default();
+        answers.set(day!(2), 2, "7");
+        answers.set(day!(1), 1, "3");
+        assert_eq!(
+            update_content(&mut s, MARKER, year!(2023), &answers).unwrap(),
+            TableUpdate::Updated
+        );
+
+        let expected = format!(
+            "foo\n{MARKER}\n## 2023 Results\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ |   |\n| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ | ⭐ |\n{MARKER}\nbar"
+        );
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn rejects_missing_table() {
+        let mut s = String::from("foo");
+        assert!(update_content(&mut s, MARKER, year!(2023), &Answers::default()).is_err());
+    }
+
+    #[test]
+    fn keeps_table_of_other_year() {
+        let table = format!("{MARKER}\n## 2022 Results\n\n| Day | Part 1 | Part 2 |\n{MARKER}");
+        let mut s = table.clone();
+
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "3");
+        assert_eq!(
+            update_content(&mut s, MARKER, year!(2023), &answers).unwrap(),
+            TableUpdate::Kept(year!(2022))
+        );
+        assert_eq!(s, table);
+    }
+}
@@ -0,0 +1,305 @@
+/// Machine-readable results of solution parts.
+/// Solution binaries write one JSON record per line to the file named by `AOC_RESULTS_FILE`, if set.
+/// This allows `run_multi` to read answers and timings without parsing the human-readable output.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::bench::{stats_from_json, stats_to_json, BenchStats};
+use crate::template::{Day, Outcome};
+
+pub const RESULTS_FILE_ENV: &str = "AOC_RESULTS_FILE";
+
+/// The `part` of records that describe the parse phase, which is shared by both parts.
+pub const PARSE: u8 = 0;
+
+/// Outcome of running a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    NoAnswer,
+    NotImplemented,
+    Error,
+    Panicked,
+}
+
+impl PartStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::NoAnswer => "no_answer",
+            PartStatus::NotImplemented => "not_implemented",
+            PartStatus::Error => "error",
+            PartStatus::Panicked => "panicked",
+        }
+    }
+}
+
+impl From<&Outcome> for PartStatus {
+    fn from(value: &Outcome) -> Self {
+        match value {
+            Outcome::Answer(_) => PartStatus::Solved,
+            Outcome::NoAnswer => PartStatus::NoAnswer,
+            Outcome::NotImplemented => PartStatus::NotImplemented,
+            Outcome::Error(_) => PartStatus::Error,
+            Outcome::Panicked(_) => PartStatus::Panicked,
+        }
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "no_answer" => Ok(PartStatus::NoAnswer),
+            "not_implemented" => Ok(PartStatus::NotImplemented),
+            "error" => Ok(PartStatus::Error),
+            "panicked" => Ok(PartStatus::Panicked),
+            s => Err(format!("Unknown part status `{s}`.")),
+        }
+    }
+}
+
+/// Represents the result of running a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub nanos: f64,
+    pub samples: u128,
+    pub status: PartStatus,
+    pub stats: Option<BenchStats>,
+}
+
+impl PartRecord {
+    /// Describe the outcome of running a part.
+    /// If the part was benched, its median duration is recorded instead of `duration`.
+    pub fn new(
+        day: Day,
+        part: u8,
+        outcome: &Outcome,
+        duration: Duration,
+        stats: Option<BenchStats>,
+    ) -> Self {
+        let (answer, error) = match outcome {
+            Outcome::Answer(answer) => (Some(answer.clone()), None),
+            Outcome::Error(e) | Outcome::Panicked(e) => (None, Some(e.clone())),
+            Outcome::NoAnswer | Outcome::NotImplemented => (None, None),
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = stats.map_or(duration.as_nanos() as f64, |s| s.median_nanos);
+
+        PartRecord {
+            day,
+            part,
+            answer,
+            error,
+            nanos,
+            samples: stats.map_or(1, |s| s.samples),
+            status: PartStatus::from(outcome),
+            stats,
+        }
+    }
+
+    /// Recover the outcome of the part from the record.
+    pub fn outcome(&self) -> Outcome {
+        match self.status {
+            PartStatus::Solved => Outcome::Answer(self.answer.clone().unwrap_or_default()),
+            PartStatus::NoAnswer => Outcome::NoAnswer,
+            PartStatus::NotImplemented => Outcome::NotImplemented,
+            PartStatus::Error => Outcome::Error(self.error.clone().unwrap_or_default()),
+            PartStatus::Panicked => Outcome::Panicked(self.error.clone().unwrap_or_default()),
+        }
+    }
+
+    /// Append the record to the results file, if one was requested via `AOC_RESULTS_FILE`.
+    pub fn emit(&self) -> Result<(), io::Error> {
+        let Ok(path) = env::var(RESULTS_FILE_ENV) else {
+            return Ok(());
+        };
+
+        let line = JsonValue::from(self)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{line}")
+    }
+}
+
+/// Read all records from a results file. If not present, returns no records.
+pub fn read_from_file(path: &Path) -> Result<Vec<PartRecord>, String> {
+    let Ok(s) = fs::read_to_string(path) else {
+        return Ok(vec![]);
+    };
+
+    parse_lines(&s)
+}
+
+fn parse_lines(s: &str) -> Result<Vec<PartRecord>, String> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json = JsonValue::from_str(line).or(Err("Record is not valid JSON."))?;
+            PartRecord::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert("stats".into(), stats_to_json(value.stats.as_ref()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| [0.0, 1.0, 2.0].contains(*part))
+            .map(|part| *part as u8)
+            .ok_or("Expected record.part to be 0, 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let error = json
+            .get("error")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.error to be null or string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|samples| *samples as u128)
+            .ok_or("Expected record.samples to be a number.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")?
+            .parse()?;
+
+        let stats = stats_from_json(json.get("stats"))?;
+
+        Ok(PartRecord {
+            day,
+            part,
+            answer: answer.cloned(),
+            error: error.cloned(),
+            nanos,
+            samples,
+            status,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_lines, PartRecord, PartStatus};
+    use crate::{day, template::Outcome};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_records() {
+        let record = PartRecord {
+            day: day!(1),
+            part: 2,
+            answer: Some("(74.13ns @ 10 samples)\nmultiline".into()),
+            error: None,
+            nanos: 74.13,
+            samples: 10,
+            status: PartStatus::Solved,
+            stats: None,
+        };
+        let line = JsonValue::from(&record).stringify().unwrap();
+        assert_eq!(parse_lines(&line).unwrap(), vec![record]);
+    }
+
+    #[test]
+    fn parses_multiple_lines() {
+        let s = [
+            r#"{ "day": "03", "part": 1, "answer": "42", "error": null, "nanos": 100, "samples": 5, "status": "solved" }"#,
+            r#"{ "day": "03", "part": 2, "answer": null, "error": "bad input", "nanos": 0, "samples": 1, "status": "error" }"#,
+            "",
+        ]
+        .join("\n");
+        let records = parse_lines(&s).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].answer, Some("42".into()));
+        assert_eq!(records[1].outcome(), Outcome::Error("bad input".into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_records() {
+        let s = r#"{ "day": "03", "part": 3, "answer": null, "error": null, "nanos": 0, "samples": 1, "status": "solved" }"#;
+        parse_lines(s).unwrap();
+    }
+}
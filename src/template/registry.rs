@@ -0,0 +1,43 @@
+/// Registry of all solutions in `src/bin`.
+/// The solutions are not compiled into the library, so that each solution binary only depends on its own source file.
+/// Instead, the runner binary includes them as modules when built with the `registry` feature, and registers them on startup, see `build.rs`.
+use std::sync::OnceLock;
+
+use crate::template::{Puzzle, Solution, Year};
+
+static SOLUTIONS: OnceLock<&'static [(Year, &'static dyn Solution)]> = OnceLock::new();
+
+/// Registers the solutions of the runner binary, before any solution is looked up. Later calls are ignored.
+pub fn register(solutions: &'static [(Year, &'static dyn Solution)]) {
+    SOLUTIONS.get_or_init(|| solutions);
+}
+
+/// Whether solutions can run in-process, i.e. the runner was built with the `registry` feature.
+pub fn is_registered() -> bool {
+    SOLUTIONS.get().is_some()
+}
+
+/// Fails for commands that run solutions in-process when the runner was built without the `registry` feature.
+pub fn check() -> Result<(), &'static str> {
+    if is_registered() {
+        Ok(())
+    } else {
+        Err("This command runs solutions in-process, build the runner with `--features registry`.")
+    }
+}
+
+/// Returns all registered solutions of `year`, ordered by day.
+pub fn solutions(year: Year) -> impl Iterator<Item = &'static dyn Solution> {
+    SOLUTIONS
+        .get()
+        .copied()
+        .unwrap_or_default()
+        .iter()
+        .filter(move |(solution_year, _)| *solution_year == year)
+        .map(|(_, solution)| *solution)
+}
+
+/// Returns the registered solution for `puzzle`, if it has been scaffolded.
+pub fn find(puzzle: Puzzle) -> Option<&'static dyn Solution> {
+    solutions(puzzle.year).find(|solution| solution.day() == puzzle.day)
+}
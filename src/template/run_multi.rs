@@ -1,90 +1,379 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::artifacts::{self, Artifacts, Profile};
+use crate::template::records::{PartRecord, PartStatus, PARSE};
+use crate::template::{registry, table, Day, Puzzle, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// How [`run_multi`] runs the solution of each day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Execution {
+    /// Run each day as its own binary, even if the solutions are registered to run in-process.
+    pub is_isolated: bool,
+    /// Number of days that run at the same time. With more than one, each day runs as its own binary.
+    pub jobs: usize,
+    /// Stop days that run for longer than this. With a timeout, each day runs as its own binary.
+    pub timeout: Option<Duration>,
+}
+
+/// The result of running the solution of a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DayOutcome {
+    /// There is no solution for the day yet.
+    NotScaffolded,
+    /// The solution ran to completion, its parts may or may not have returned an answer.
+    Solved(Vec<PartRecord>),
+    /// The solution could not run, crashed, or one of its parts panicked or returned an error.
+    Failed(String, Vec<PartRecord>),
+    /// The solution was stopped after running for longer than the timeout.
+    TimedOut(Duration),
+}
+
+impl DayOutcome {
+    /// Classify a solution that ran to completion by the status of its parts.
+    pub fn from_records(records: Vec<PartRecord>) -> Self {
+        let failed = records
+            .iter()
+            .find(|r| matches!(r.status, PartStatus::Panicked | PartStatus::Error));
+
+        let Some(record) = failed else {
+            return DayOutcome::Solved(records);
+        };
+
+        let phase = match record.part {
+            PARSE => "parse".to_string(),
+            part => format!("part {part}"),
+        };
+        let status = record.status.as_str();
+        let reason = match &record.error {
+            Some(error) => format!("{phase} {status}: {error}"),
+            None => format!("{phase} {status}"),
+        };
+
+        DayOutcome::Failed(reason, records)
+    }
 
-    let mut need_space = false;
+    pub fn records(&self) -> &[PartRecord] {
+        match self {
+            DayOutcome::Solved(records) | DayOutcome::Failed(_, records) => records,
+            DayOutcome::NotScaffolded | DayOutcome::TimedOut(_) => &[],
+        }
+    }
 
+    pub fn is_failure(&self) -> bool {
+        matches!(self, DayOutcome::Failed(..) | DayOutcome::TimedOut(_))
+    }
+
+    fn print(&self) {
+        match self {
+            DayOutcome::NotScaffolded => println!("Not solved."),
+            DayOutcome::Solved(_) => {}
+            DayOutcome::Failed(reason, _) => println!("Failed: {reason}"),
+            DayOutcome::TimedOut(timeout) => println!("Timed out after {timeout:?}."),
+        }
+    }
+
+    /// The result and details columns of the summary table.
+    fn summary(&self) -> [String; 2] {
+        match self {
+            DayOutcome::NotScaffolded => ["- not scaffolded".into(), String::new()],
+            DayOutcome::Solved(_) => ["✔ solved".into(), String::new()],
+            DayOutcome::Failed(reason, _) => ["✖ failed".into(), reason.clone()],
+            DayOutcome::TimedOut(timeout) => ["⏱ timed out".into(), format!("after {timeout:?}")],
+        }
+    }
+}
+
+/// The result of running several days with [`run_multi`].
+pub struct Report {
+    /// Timings of all days that ran, only measured for timed runs.
+    pub timings: Option<Timings>,
+    /// Number of days that failed or timed out.
+    pub failures: usize,
+}
+
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    profile: Profile,
+    is_timed: bool,
+    execution: Execution,
+) -> Report {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let puzzles: Vec<Puzzle> = all_days()
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+        .map(|day| Puzzle::new(year, day))
+        .collect();
+
+    // heap profiling needs the global allocator of the `dhat-heap` feature, which only the built bins have.
+    let is_in_process = registry::is_registered()
+        && !execution.is_isolated
+        && execution.jobs == 1
+        && execution.timeout.is_none()
+        && profile != Profile::Dhat;
+
+    let outcomes = if is_in_process {
+        run_sequential(&puzzles, |puzzle| {
+            in_process::run_solution(puzzle, is_timed)
+        })
+    } else {
+        let artifacts = match artifacts::build(profile) {
+            Ok(artifacts) => artifacts,
+            Err(e) => {
+                eprintln!("Failed to build solutions: {e}");
+                std::process::exit(1);
             }
-            need_space = true;
+        };
+        artifacts.print_errors();
+
+        if execution.jobs > 1 {
+            run_parallel(&puzzles, &artifacts, is_timed, execution)
+        } else {
+            run_sequential(&puzzles, |puzzle| {
+                child_commands::run_built(&artifacts, puzzle, is_timed, execution.timeout, false)
+                    .map_or_else(
+                        |e| DayOutcome::Failed(e.to_string(), vec![]),
+                        |run| run.outcome,
+                    )
+            })
+        }
+    };
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    print_summary(&outcomes);
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let failures = outcomes
+        .iter()
+        .filter(|(_, outcome)| outcome.is_failure())
+        .count();
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    let timings = is_timed.then(|| Timings {
+        data: outcomes
+            .iter()
+            .filter(|(_, outcome)| !outcome.records().is_empty())
+            .map(|(puzzle, outcome)| Timing::from_records(puzzle.day, outcome.records()))
+            .collect(),
+    });
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    if let Some(timings) = &timings {
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
     }
+
+    Report { timings, failures }
+}
+
+fn print_header(puzzle: Puzzle, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day);
+    println!("------");
+}
+
+fn print_summary(outcomes: &[(Puzzle, DayOutcome)]) {
+    let rows: Vec<[String; 3]> = outcomes
+        .iter()
+        .map(|(puzzle, outcome)| {
+            let [result, details] = outcome.summary();
+            [puzzle.day.to_string(), result, details]
+        })
+        .collect();
+
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+    table::print(["Day", "Result", "Details"], &rows);
+}
+
+fn run_sequential(
+    puzzles: &[Puzzle],
+    run_solution: impl Fn(Puzzle) -> DayOutcome,
+) -> Vec<(Puzzle, DayOutcome)> {
+    let mut outcomes: Vec<(Puzzle, DayOutcome)> = Vec::with_capacity(puzzles.len());
+
+    for (index, puzzle) in puzzles.iter().enumerate() {
+        print_header(*puzzle, index > 0);
+
+        let outcome = run_solution(*puzzle);
+
+        outcome.print();
+        outcomes.push((*puzzle, outcome));
+    }
+
+    outcomes
+}
+
+/// Run up to `jobs` of the built solutions at the same time.
+/// The output of each day is captured and printed in order of the days, as soon as all earlier days are done.
+fn run_parallel(
+    puzzles: &[Puzzle],
+    artifacts: &Artifacts,
+    is_timed: bool,
+    execution: Execution,
+) -> Vec<(Puzzle, DayOutcome)> {
+    let mut outcomes: Vec<(Puzzle, DayOutcome)> = Vec::with_capacity(puzzles.len());
+    let next = &AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..execution.jobs.min(puzzles.len()) {
+            let sender = sender.clone();
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(puzzle) = puzzles.get(index) else {
+                    break;
+                };
+
+                let run = child_commands::run_built(
+                    artifacts,
+                    *puzzle,
+                    is_timed,
+                    execution.timeout,
+                    true,
+                );
+                if sender.send((index, run)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut runs: Vec<Option<Result<child_commands::CapturedRun, Error>>> =
+            puzzles.iter().map(|_| None).collect();
+
+        for (index, run) in receiver {
+            runs[index] = Some(run);
+
+            while let Some(Some(run)) = runs.get_mut(outcomes.len()).map(Option::take) {
+                let puzzle = puzzles[outcomes.len()];
+                print_header(puzzle, !outcomes.is_empty());
+
+                let outcome = match run {
+                    Ok(run) => {
+                        let _ = io::stdout().write_all(&run.stdout);
+                        let _ = io::stderr().write_all(&run.stderr);
+                        run.outcome
+                    }
+                    Err(e) => DayOutcome::Failed(e.to_string(), vec![]),
+                };
+
+                outcome.print();
+                outcomes.push((puzzle, outcome));
+            }
+        }
+    });
+
+    outcomes
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
+    Records(String),
     IO(io::Error),
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Records(e) => write!(f, "failed to read result records: {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Solutions are also compiled into the runner binary via the solution registry, if it is built with the `registry` feature.
+/// This module runs them without spawning any child processes.
+pub mod in_process {
+    use super::DayOutcome;
+    use crate::template::{registry, runner, Puzzle};
+    use std::fs;
+
+    /// Run the registered solution for a given puzzle.
+    pub fn run_solution(puzzle: Puzzle, is_timed: bool) -> DayOutcome {
+        let Some(solution) = registry::find(puzzle) else {
+            return DayOutcome::NotScaffolded;
+        };
+
+        let input_path = match puzzle.input_path() {
+            Ok(input_path) => input_path,
+            Err(e) => return DayOutcome::Failed(e.to_string(), vec![]),
+        };
+        let input = match fs::read_to_string(&input_path) {
+            Ok(input) => input,
+            Err(e) => {
+                let reason = format!("could not open input file \"{input_path}\": {e}");
+                return DayOutcome::Failed(reason, vec![]);
+            }
+        };
+
+        DayOutcome::from_records(runner::run_solution(solution, &input, is_timed))
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their result records.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{DayOutcome, Error};
+    use crate::template::artifacts::Artifacts;
+    use crate::template::records::{self, PartRecord, RESULTS_FILE_ENV};
+    use crate::template::{cargo_command, Puzzle};
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        env, fs,
+        io::Read,
+        path::{Path, PathBuf},
+        process::{self, Child, Command, ExitStatus, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// How often a child that runs with a timeout is checked for having exited.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// The output and outcome of a solution binary that ran with captured output.
+    pub struct CapturedRun {
+        pub stdout: Vec<u8>,
+        pub stderr: Vec<u8>,
+        pub outcome: DayOutcome,
+    }
+
+    /// Run the solution bin for a given puzzle
+    pub fn run_solution(
+        puzzle: Puzzle,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<DayOutcome, Error> {
+        // skip command invocation for days that have not been scaffolded yet or cannot run.
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(DayOutcome::NotScaffolded);
+        }
+        if let Some(outcome) = check_input(puzzle) {
+            return Ok(outcome);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = puzzle.bin_name();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
@@ -96,162 +385,208 @@ pub mod child_commands {
             args.push("--time");
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        let results_path = get_results_path(puzzle);
 
-        let mut cmd = Command::new("cargo")
+        let status = cargo_command()
             .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .env(RESULTS_FILE_ENV, &results_path)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        Ok(classify(status, read_records(&results_path)?))
+    }
 
-        let mut output = vec![];
+    /// Run the built solution bin for a given puzzle directly, optionally capturing its output.
+    /// The bin is killed once it runs for longer than `timeout`.
+    pub fn run_built(
+        artifacts: &Artifacts,
+        puzzle: Puzzle,
+        is_timed: bool,
+        timeout: Option<Duration>,
+        is_captured: bool,
+    ) -> Result<CapturedRun, Error> {
+        let skipped = |outcome| CapturedRun {
+            stdout: vec![],
+            stderr: vec![],
+            outcome,
+        };
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        // skip days that have not been scaffolded yet or cannot run.
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(skipped(DayOutcome::NotScaffolded));
+        }
+        if artifacts.has_errors(puzzle) {
+            return Ok(skipped(DayOutcome::Failed(
+                "does not compile".into(),
+                vec![],
+            )));
+        }
+        let Some(executable) = artifacts.executable(puzzle) else {
+            let reason = "was not built, see the compile errors above".into();
+            return Ok(skipped(DayOutcome::Failed(reason, vec![])));
+        };
+        if let Some(outcome) = check_input(puzzle) {
+            return Ok(skipped(outcome));
+        }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let mut cmd = Command::new(executable);
+
+        if is_timed {
+            cmd.arg("--time");
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let results_path = get_results_path(puzzle);
+
+        let mut child = cmd
+            .env(RESULTS_FILE_ENV, &results_path)
+            .stdout(output(is_captured))
+            .stderr(output(is_captured))
+            .spawn()?;
+
+        // drain the pipes while waiting, a child that fills them would block until it is killed.
+        let stdout = read_to_end(child.stdout.take());
+        let stderr = read_to_end(child.stderr.take());
 
-        Ok(output)
+        let outcome = match wait_with_timeout(&mut child, timeout)? {
+            Some(status) => classify(status, read_records(&results_path)?),
+            None => {
+                let _ = fs::remove_file(&results_path);
+                DayOutcome::TimedOut(timeout.unwrap_or_default())
+            }
+        };
+
+        Ok(CapturedRun {
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+            outcome,
+        })
+    }
+
+    fn output(is_captured: bool) -> Stdio {
+        if is_captured {
+            Stdio::piped()
+        } else {
+            Stdio::inherit()
+        }
+    }
+
+    fn read_to_end(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut buf = vec![];
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    /// Wait for the child to exit. Returns `None` if it was killed after running for longer than `timeout`.
+    fn wait_with_timeout(
+        child: &mut Child,
+        timeout: Option<Duration>,
+    ) -> Result<Option<ExitStatus>, Error> {
+        let Some(timeout) = timeout else {
+            return Ok(Some(child.wait()?));
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        let deadline = Instant::now() + timeout;
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Some(status));
+            }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            if Instant::now() >= deadline {
+                child.kill()?;
+                child.wait()?;
+                return Ok(None);
+            }
 
-                timings.total_nanos += nanos;
-            });
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// A day whose input is missing fails without running it.
+    fn check_input(puzzle: Puzzle) -> Option<DayOutcome> {
+        let input_path = match puzzle.input_path() {
+            Ok(input_path) => input_path,
+            Err(e) => return Some(DayOutcome::Failed(e.to_string(), vec![])),
+        };
+        if !Path::new(&input_path).exists() {
+            let reason = format!("input file \"{input_path}\" does not exist");
+            return Some(DayOutcome::Failed(reason, vec![]));
+        }
 
-        timings
+        None
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// A child that exits unsuccessfully failed, even if some of its parts ran before it crashed.
+    fn classify(status: ExitStatus, records: Vec<PartRecord>) -> DayOutcome {
+        let reason = match status.code() {
+            _ if status.success() => return DayOutcome::from_records(records),
+            Some(code) => format!("exited with code {code}"),
+            None => "killed by a signal".into(),
+        };
+
+        DayOutcome::Failed(reason, records)
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// The child appends one record per part to this file.
+    fn get_results_path(puzzle: Puzzle) -> PathBuf {
+        let results_path = env::temp_dir().join(format!(
+            "advent_of_code-{}-{}.jsonl",
+            process::id(),
+            puzzle.bin_name()
+        ));
+        let _ = fs::remove_file(&results_path);
+        results_path
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    fn read_records(results_path: &Path) -> Result<Vec<PartRecord>, Error> {
+        let records = records::read_from_file(results_path).map_err(Error::Records);
+        let _ = fs::remove_file(results_path);
+        records
     }
+}
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DayOutcome;
+    use crate::day;
+    use crate::template::records::{PartRecord, PartStatus};
+
+    fn record(part: u8, status: PartStatus, error: Option<&str>) -> PartRecord {
+        PartRecord {
+            day: day!(1),
+            part,
+            answer: (status == PartStatus::Solved).then(|| "42".into()),
+            error: error.map(String::from),
+            nanos: 1.0,
+            samples: 1,
+            status,
+            stats: None,
         }
+    }
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn classifies_records() {
+        let solved = vec![
+            record(1, PartStatus::Solved, None),
+            record(2, PartStatus::NotImplemented, None),
+        ];
+        assert_eq!(
+            DayOutcome::from_records(solved.clone()),
+            DayOutcome::Solved(solved)
+        );
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+        let failed = vec![
+            record(1, PartStatus::Solved, None),
+            record(2, PartStatus::Panicked, Some("index out of bounds")),
+        ];
+        assert_eq!(
+            DayOutcome::from_records(failed.clone()),
+            DayOutcome::Failed("part 2 panicked: index out of bounds".into(), failed)
+        );
     }
 }
@@ -1,35 +1,313 @@
 /// Encapsulates code that interacts with solution functions.
-use std::fmt::Display;
+use std::cell::RefCell;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::Answers;
+use crate::template::aoc_client::{self, Backend, Verdict};
+use crate::template::bench::{BenchConfig, BenchStats};
+use crate::template::config;
+use crate::template::ledger::{Entry, Ledger};
+use crate::template::readme_stars::{self, TableUpdate};
+use crate::template::records::{PartRecord, PartStatus, PARSE};
+use crate::template::{
+    aoc_cli, Day, InputSource, Outcome, PartOutput, Puzzle, Solution, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::template::{unix_timestamp, ANSI_BOLD};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Read the input selected by `--input` or `--example`, or the puzzle input of the day.
+/// Any other source is shown before the output of the parts.
+pub fn read_input(puzzle: Puzzle) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let source = InputSource::from_args(&args).unwrap_or_else(|e| {
+        eprintln!("Unexpected command-line input: {e}");
+        process::exit(1);
+    });
+
+    if source != InputSource::Puzzle {
+        let description = source.describe(puzzle).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+        println!("{ANSI_BOLD}Input:{ANSI_RESET} {description}");
+    }
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    source.read(puzzle).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+pub fn run_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+    reset: fn(),
+) {
+    let bench_config = env::args()
+        .any(|x| x == "--time")
+        .then(|| config::get().bench);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    let record = execute_part(func, input, puzzle.day, part, reset, bench_config.as_ref());
+
+    if let Err(e) = record.emit() {
+        eprintln!("failed to write result record: {e}");
+    }
+
+    if let Outcome::Answer(answer) = record.outcome() {
+        submit_result(answer, puzzle, part);
     }
 }
 
+/// Run the parse function of a solution, returning its output or the panic message if it panicked.
+pub fn run_parse<T>(parse: impl Fn(&str) -> T, input: &str, puzzle: Puzzle) -> Result<T, String> {
+    let bench_config = env::args()
+        .any(|x| x == "--time")
+        .then(|| config::get().bench);
+
+    let (parsed, record) = execute_parse(parse, input, puzzle.day, bench_config.as_ref());
+
+    if let Err(e) = record.emit() {
+        eprintln!("failed to write result record: {e}");
+    }
+
+    parsed
+}
+
+/// Report a part that could not run because parsing the input panicked.
+pub fn skip_part(puzzle: Puzzle, part: u8) {
+    let record = skipped_part(puzzle.day, part);
+
+    if let Err(e) = record.emit() {
+        eprintln!("failed to write result record: {e}");
+    }
+}
+
+/// Run both parts of a registered solution in the current process and collect their results.
+pub fn run_solution(solution: &dyn Solution, input: &str, is_timed: bool) -> Vec<PartRecord> {
+    let day = solution.day();
+    let bench_config = is_timed.then(|| config::get().bench);
+    let bench_config = bench_config.as_ref();
+
+    let mut records = vec![];
+
+    let parsed = if solution.has_parse() {
+        let (parsed, record) =
+            execute_parse(|input| solution.parse(input), input, day, bench_config);
+        records.push(record);
+
+        let Ok(parsed) = parsed else {
+            records.extend([skipped_part(day, 1), skipped_part(day, 2)]);
+            return records;
+        };
+        parsed
+    } else {
+        solution.parse(input)
+    };
+
+    let reset = || solution.reset();
+
+    records.extend([
+        execute_part(
+            |input| solution.part_one(input),
+            &*parsed,
+            day,
+            1,
+            reset,
+            bench_config,
+        ),
+        execute_part(
+            |input| solution.part_two(input),
+            &*parsed,
+            day,
+            2,
+            reset,
+            bench_config,
+        ),
+    ]);
+
+    records
+}
+
+/// Run parts of a registered solution without printing anything, e.g. to check them against an example.
+/// Like [`run_solution`], the input is parsed once for all parts, which are skipped if parsing panics.
+/// Panics if a part is not 1 or 2.
+pub fn run_solution_quietly(solution: &dyn Solution, input: &str, parts: &[u8]) -> Vec<Outcome> {
+    if let Some(part) = parts.iter().find(|part| !matches!(part, 1 | 2)) {
+        panic!("expecting part 1 or 2, got {part}");
+    }
+
+    let Ok(parsed) = catch_panic(|| solution.parse(input)) else {
+        return parts.iter().map(|_| skipped_outcome()).collect();
+    };
+
+    parts
+        .iter()
+        .map(|&part| {
+            solution.reset();
+            catch_panic(|| match part {
+                1 => solution.part_one(&*parsed),
+                2 => solution.part_two(&*parsed),
+                _ => unreachable!("parts are checked above"),
+            })
+            .unwrap_or_else(Outcome::Panicked)
+        })
+        .collect()
+}
+
+/// Run the parse function of a solution, print its duration and describe it as a [`PartRecord`].
+fn execute_parse<T>(
+    parse: impl Fn(&str) -> T,
+    input: &str,
+    day: Day,
+    bench_config: Option<&BenchConfig>,
+) -> (Result<T, String>, PartRecord) {
+    let run = catch_panic(|| {
+        run_timed(parse, input, &|| {}, bench_config, |_| {
+            print!("Parse:");
+        })
+    });
+
+    match run {
+        Ok((parsed, base_time, duration, stats)) => {
+            print!("\r");
+            println!("Parse:{}", format_duration(&duration, stats.as_ref()));
+            print_stats(stats.as_ref(), base_time);
+
+            let record = PartRecord {
+                answer: None,
+                status: PartStatus::Solved,
+                ..PartRecord::new(day, PARSE, &Outcome::NoAnswer, duration, stats)
+            };
+
+            (Ok(parsed), record)
+        }
+        Err(message) => {
+            print!("\r");
+            println!("Parse: panicked: {message}");
+
+            let outcome = Outcome::Panicked(message.clone());
+            let record = PartRecord::new(day, PARSE, &outcome, Duration::ZERO, None);
+
+            (Err(message), record)
+        }
+    }
+}
+
+/// Describe a part that could not run because parsing the input panicked.
+fn skipped_part(day: Day, part: u8) -> PartRecord {
+    println!("Part {part}: {ANSI_ITALIC}skipped{ANSI_RESET}");
+    PartRecord::new(day, part, &skipped_outcome(), Duration::ZERO, None)
+}
+
+fn skipped_outcome() -> Outcome {
+    Outcome::Panicked("skipped, because parsing the input panicked".into())
+}
+
+/// Run a single solution part, print its outcome and describe it as a [`PartRecord`].
+fn execute_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+    reset: impl Fn(),
+    bench_config: Option<&BenchConfig>,
+) -> PartRecord {
+    let part_str = format!("Part {part}");
+
+    let run = catch_panic(|| {
+        run_timed(func, input, &reset, bench_config, |result| {
+            print_result(&result.outcome(), &part_str, "");
+        })
+    });
+
+    let (outcome, base_time, duration, stats) = match run {
+        Ok((result, base_time, duration, stats)) => (result.outcome(), base_time, duration, stats),
+        Err(message) => (
+            Outcome::Panicked(message),
+            Duration::ZERO,
+            Duration::ZERO,
+            None,
+        ),
+    };
+
+    print_result(
+        &outcome,
+        &part_str,
+        &format_duration(&duration, stats.as_ref()),
+    );
+
+    print_stats(stats.as_ref(), base_time);
+
+    PartRecord::new(day, part, &outcome, duration, stats)
+}
+
+thread_local! {
+    /// Set while a part is running, the panic hook stores the message of a panic here instead of printing it.
+    static CAUGHT_PANIC: RefCell<Option<Option<String>>> = const { RefCell::new(None) };
+}
+
+/// Run `func`, catching a panic and returning its message and location instead of unwinding further.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static INSTALL_HOOK: Once = Once::new();
+
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let message = info
+                .payload()
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| info.payload().downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Box<dyn Any>".into());
+
+            let message = match info.location() {
+                Some(location) => format!("{message} ({location})"),
+                None => message,
+            };
+
+            let is_caught = CAUGHT_PANIC.with_borrow_mut(|caught| match caught {
+                Some(slot) => {
+                    *slot = Some(message);
+                    true
+                }
+                None => false,
+            });
+
+            if !is_caught {
+                default_hook(info);
+            }
+        }));
+    });
+
+    CAUGHT_PANIC.set(Some(None));
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    let message = CAUGHT_PANIC.take().flatten();
+
+    result.map_err(|_| message.unwrap_or_else(|| "unknown panic".into()))
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched according to `bench_config` and the median duration is reported.
+///
+/// `reset` is called before every run, outside of the measured time.
+/// Returns the result, the duration of the first run, the reported duration and the bench statistics.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    reset: &impl Fn(),
+    bench_config: Option<&BenchConfig>,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Duration, Option<BenchStats>) {
+    reset();
+
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -43,27 +321,39 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+    match bench_config {
+        Some(config) => {
+            let stats = bench(func, input, reset, &base_time, config);
+            (result, base_time, stats.median(), Some(stats))
+        }
+        None => (result, base_time, base_time, None),
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    reset: &impl Fn(),
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> BenchStats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = config.samples_for(*base_time);
+    let warmup_iterations = config.warmup_for(bench_iterations);
+
+    for _ in 0..warmup_iterations {
+        reset();
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
+        reset();
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
@@ -71,76 +361,75 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers, warmup_iterations)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(duration: &Duration, stats: Option<&BenchStats>) -> String {
+    match stats {
+        Some(stats) => format!(" ({duration:.1?} @ {} samples)", stats.samples),
+        None => format!(" ({duration:.1?})"),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+/// Print the bench statistics of a part, warning if its samples ran suspiciously fast.
+fn print_stats(stats: Option<&BenchStats>, base_time: Duration) {
+    let Some(stats) = stats else {
+        return;
+    };
+
+    println!("        {ANSI_ITALIC}{stats}{ANSI_RESET}");
+
+    if stats.is_much_faster_than(base_time) {
+        eprintln!(
+            "        warning: samples ran much faster than the first run ({base_time:.1?}). \
+            If the part memoizes results, pass a `reset` function to `solution!` to clear the cache between runs."
+        );
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(outcome: &Outcome, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
+    let str = match outcome {
+        Outcome::Answer(result) => {
+            if result.contains('\n') {
+                if !is_intermediate_result {
                     print!("\r");
-                    println!("{str}");
+                    println!("{part}: ▼ {duration_str}");
                     println!("{result}");
+                    return;
                 }
+                format!("{part}: ▼ {duration_str}")
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}")
             }
         }
+        Outcome::NoAnswer => format!("{part}: ✖"),
+        Outcome::NotImplemented => format!("{part}: {ANSI_ITALIC}not implemented{ANSI_RESET}"),
+        Outcome::Error(e) => format!("{part}: error: {e}"),
+        Outcome::Panicked(message) => format!("{part}: panicked: {message}"),
+    };
+
+    if is_intermediate_result {
+        print!("{str}");
+    } else if matches!(outcome, Outcome::Answer(_)) {
+        print!("\r");
+        println!("{str}");
+    } else {
+        print!("\r");
+        println!("{str}             ");
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. the `--submit` flag names this part.
+///  3. the ledger of earlier submissions does not rule out the answer, unless `--force` is passed or the check is disabled.
+fn submit_result(result: String, puzzle: Puzzle, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -156,14 +445,123 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if InputSource::from_args(&args).is_ok_and(|source| source != InputSource::Puzzle) {
+        eprintln!(
+            "Not submitting {result}: it was computed from another input than the puzzle input."
+        );
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let config = config::get();
+
+    let ledger = match Ledger::read_from_file(puzzle.year) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("failed to read submission ledger: {e}");
+            process::exit(1);
+        }
+    };
+
+    if config.submit.check_ledger && !args.contains(&"--force".into()) {
+        if let Err(refusal) = ledger.check(puzzle.day, part, &result, unix_timestamp()) {
+            eprintln!("Not submitting {result}: {refusal} Pass --force to submit anyway.");
+            process::exit(1);
+        }
+    }
+
+    let record = |verdict: Verdict| {
+        let entry = Entry::now(puzzle.day, part, &result, verdict);
+        if let Err(e) = Ledger::append(puzzle.year, &entry) {
+            eprintln!("failed to record submission: {e}");
+        }
+    };
+
+    match config.backend {
+        Backend::Native => {
+            println!("Submitting result...");
+            match aoc_client::submit(puzzle, part, &result) {
+                Ok(submission) => {
+                    record(submission.verdict);
+
+                    if submission.verdict == Verdict::Correct {
+                        follow_up_correct(puzzle, part, &result);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("failed to submit result: {e}");
+                    process::exit(1);
+                }
+            }
+        }
+        Backend::AocCli => {
+            if aoc_cli::check().is_err() {
+                eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+                process::exit(1);
+            }
+
+            println!("Submitting result via aoc-cli...");
+            if let Err(e) = aoc_cli::submit(puzzle, part, &result) {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+
+            // aoc-cli prints the verdict without returning it, so only the attempt is recorded.
+            record(Verdict::Unknown);
+        }
+    }
+}
+
+/// After a correct submission, fetch the now unlocked puzzle text, store the accepted answer and add its star to the readme.
+/// Only the built-in client returns the verdict of a submission, so this does not run for submissions via aoc-cli.
+fn follow_up_correct(puzzle: Puzzle, part: u8, answer: &str) {
+    if let Err(e) = aoc_client::refresh_puzzle(puzzle) {
+        eprintln!("failed to fetch puzzle description: {e}");
+    }
+
+    let mut answers = match Answers::read_from_file(puzzle.year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("failed to read accepted answers, not storing the answer: {e}");
+            return;
+        }
+    };
+    answers.set(puzzle.day, part, answer);
+
+    if let Err(e) = answers.store_file(puzzle.year) {
+        eprintln!("failed to store accepted answer: {e}");
+        return;
+    }
+    println!("🎄 Stored accepted answer.");
+
+    if !config::get().submit.update_readme {
+        return;
+    }
+
+    match readme_stars::update(puzzle.year, &answers) {
+        Ok(TableUpdate::Updated) => println!("🎄 Updated results table in README."),
+        Ok(TableUpdate::Kept(year)) => {
+            println!("🎄 Kept results table in README, it shows the results of {year}.");
+        }
+        Err(e) => eprintln!("failed to update README: {e}"),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::catch_panic;
+
+    #[test]
+    fn returns_values() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+    }
+
+    #[test]
+    fn catches_panics() {
+        let result = catch_panic(|| -> u32 { panic!("LOOP") });
+        let message = result.unwrap_err();
+        assert!(message.starts_with("LOOP (src/template/runner.rs:"));
+    }
 }
@@ -0,0 +1,98 @@
+use std::any::Any;
+use std::fmt::Display;
+
+use crate::template::Day;
+
+/// The outcome of running a single solution part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The part returned an answer.
+    Answer(String),
+    /// The part ran, but did not return an answer.
+    NoAnswer,
+    /// The part has not been implemented.
+    NotImplemented,
+    /// The part returned an error.
+    Error(String),
+    /// The part panicked, with the panic message and location.
+    Panicked(String),
+}
+
+/// Return values of solution parts that can be reported by the runner.
+///
+/// Parts can either return an [`Option`], where [`None`] signals that there is no answer,
+/// or a [`Result`], where the error message is reported in place of the answer.
+pub trait PartOutput {
+    fn outcome(&self) -> Outcome;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn outcome(&self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Answer(answer.to_string()),
+            None => Outcome::NoAnswer,
+        }
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    fn outcome(&self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::Answer(answer.to_string()),
+            Err(e) => Outcome::Error(e.to_string()),
+        }
+    }
+}
+
+impl PartOutput for Outcome {
+    fn outcome(&self) -> Outcome {
+        self.clone()
+    }
+}
+
+/// A solution for a single day of advent.
+///
+/// Implementations are generated by the [`solution!`](crate::solution) macro and collected in the
+/// [`registry`](crate::template::registry), which allows running any set of days in-process.
+pub trait Solution: Sync {
+    /// The day this solution solves.
+    fn day(&self) -> Day;
+
+    /// Prepares the puzzle input that is passed to both parts.
+    fn parse(&self, input: &str) -> Box<dyn Any> {
+        Box::new(raw_input(input))
+    }
+
+    /// Whether the solution has a dedicated parse function, which the runner times separately from the parts.
+    fn has_parse(&self) -> bool {
+        false
+    }
+
+    /// Solves part one for the output of [`Solution::parse`].
+    fn part_one(&self, _input: &dyn Any) -> Outcome {
+        Outcome::NotImplemented
+    }
+
+    /// Solves part two for the output of [`Solution::parse`].
+    fn part_two(&self, _input: &dyn Any) -> Outcome {
+        Outcome::NotImplemented
+    }
+
+    /// Clears state that is kept between runs of a part, such as memoization caches.
+    /// Called before every run, so benchmarks measure each sample from a cold cache.
+    fn reset(&self) {}
+}
+
+/// The parse function of solutions that do not declare one, passing the input on as-is.
+pub fn raw_input(input: &str) -> String {
+    input.to_string()
+}
+
+/// Recovers the output of `parse` from the type-erased input passed to [`Solution`] parts.
+/// Taking the parse function allows the [`solution!`](crate::solution) macro to infer the output type.
+#[doc(hidden)]
+pub fn parsed_input<T: 'static>(_parse: impl Fn(&str) -> T, input: &dyn Any) -> &T {
+    input
+        .downcast_ref::<T>()
+        .expect("input should be the output of `parse`")
+}
@@ -0,0 +1,40 @@
+/// Prints the tables of the `verify`, `status`, `inputs`, `all` and `time` commands.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Print `rows` below a bold `header`, with each column padded to its widest cell.
+pub fn print<const N: usize>(header: [&str; N], rows: &[[String; N]]) {
+    let mut widths = header.map(|cell| cell.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    println!("{ANSI_BOLD}{}{ANSI_RESET}", format_row(&header, &widths));
+    for row in rows {
+        println!("{}", format_row(row, &widths));
+    }
+}
+
+fn format_row(row: &[impl AsRef<str>], widths: &[usize]) -> String {
+    row.iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:<width$}", cell.as_ref()))
+        .collect::<Vec<_>>()
+        .join("  ")
+        .trim_end()
+        .to_string()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_row;
+
+    #[test]
+    fn pads_cells_to_column_width() {
+        assert_eq!(format_row(&["01", "✔ pass", ""], &[3, 8, 5]), "01   ✔ pass");
+        assert_eq!(format_row(&["Day", "Result"], &[3, 6]), "Day  Result");
+    }
+}
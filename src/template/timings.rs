@@ -1,19 +1,96 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::bench::{stats_from_json, stats_to_json, BenchStats};
+use crate::template::config::ConfigError;
+use crate::template::records::{PartRecord, PartStatus, PARSE};
+use crate::template::{Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+fn get_file_path(year: Year) -> Result<String, ConfigError> {
+    Ok(format!("{}/{TIMINGS_FILE_NAME}", year.data_dir()?))
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    pub parse: Option<String>,
+    pub parse_stats: Option<BenchStats>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_status: PartStatus,
+    pub part_2_status: PartStatus,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Collect the timings of the parse phase and all solved parts of a day from their result records.
+    /// Parts without a record were not run and are considered not implemented.
+    pub fn from_records(day: Day, records: &[PartRecord]) -> Self {
+        let mut timing = Timing {
+            day,
+            parse: None,
+            parse_stats: None,
+            part_1: None,
+            part_2: None,
+            part_1_status: PartStatus::NotImplemented,
+            part_2_status: PartStatus::NotImplemented,
+            part_1_stats: None,
+            part_2_stats: None,
+            total_nanos: 0_f64,
+        };
+
+        for record in records {
+            if record.day != day {
+                continue;
+            }
+
+            if record.part == PARSE {
+                if record.status == PartStatus::Solved {
+                    timing.parse = Some(format_nanos(record.nanos));
+                    timing.parse_stats = record.stats;
+                    timing.total_nanos += record.nanos;
+                }
+                continue;
+            }
+
+            let (duration_str, status, stats) = match record.part {
+                1 => (
+                    &mut timing.part_1,
+                    &mut timing.part_1_status,
+                    &mut timing.part_1_stats,
+                ),
+                2 => (
+                    &mut timing.part_2,
+                    &mut timing.part_2_status,
+                    &mut timing.part_2_stats,
+                ),
+                _ => continue,
+            };
+
+            *status = record.status;
+            *stats = record.stats;
+
+            if record.status == PartStatus::Solved {
+                *duration_str = Some(format_nanos(record.nanos));
+                timing.total_nanos += record.nanos;
+            }
+        }
+
+        timing
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    let duration = Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,17 +99,18 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings of `year` to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(get_file_path(year)?)?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings of `year` from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = get_file_path(year)
             .map_err(|x| x.to_string())
+            .and_then(|path| fs::read_to_string(path).map_err(|x| x.to_string()))
             .and_then(Timings::try_from);
 
         match s {
@@ -58,7 +136,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -121,6 +199,19 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        map.insert(
+            "parse".into(),
+            match value.parse.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "parse_stats".into(),
+            stats_to_json(value.parse_stats.as_ref()),
+        );
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -140,6 +231,26 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_status".into(),
+            JsonValue::String(value.part_1_status.as_str().into()),
+        );
+
+        map.insert(
+            "part_2_status".into(),
+            JsonValue::String(value.part_2_status.as_str().into()),
+        );
+
+        map.insert(
+            "part_1_stats".into(),
+            stats_to_json(value.part_1_stats.as_ref()),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            stats_to_json(value.part_2_stats.as_ref()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -158,6 +269,17 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // timings stored before the parse phase was introduced do not contain it.
+        let parse = match json.get("parse") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+            _ => None,
+        };
+
+        let parse_stats = stats_from_json(json.get("parse_stats"))?;
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -168,6 +290,16 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // timings stored before part statuses were introduced only contain solved parts.
+        let part_1_status = parse_status(json.get("part_1_status"), part_1.is_some())
+            .ok_or("Expected timing.part_1_status to be a part status.")?;
+
+        let part_2_status = parse_status(json.get("part_2_status"), part_2.is_some())
+            .ok_or("Expected timing.part_2_status to be a part status.")?;
+
+        let part_1_stats = stats_from_json(json.get("part_1_stats"))?;
+        let part_2_stats = stats_from_json(json.get("part_2_stats"))?;
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
@@ -175,18 +307,32 @@ impl TryFrom<&JsonValue> for Timing {
 
         Ok(Timing {
             day,
+            parse: parse.cloned(),
+            parse_stats,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_status,
+            part_2_status,
+            part_1_stats,
+            part_2_stats,
             total_nanos,
         })
     }
 }
 
+fn parse_status(value: Option<&JsonValue>, is_solved: bool) -> Option<PartStatus> {
+    match value {
+        Some(v) => v.get::<String>()?.parse().ok(),
+        None if is_solved => Some(PartStatus::Solved),
+        None => Some(PartStatus::NoAnswer),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::{day, template::records::PartStatus};
 
     use super::{Timing, Timings};
 
@@ -195,20 +341,38 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::NoAnswer,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -216,7 +380,7 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{day, template::records::PartStatus, template::timings::Timings};
 
         #[test]
         fn handles_json_timings() {
@@ -227,6 +391,7 @@ mod tests {
             assert_eq!(timing.day, day!(1));
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
+            assert_eq!(timing.part_2_status, PartStatus::NoAnswer);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
@@ -278,6 +443,7 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
+            template::records::PartStatus,
             template::timings::{Timing, Timings},
         };
 
@@ -286,13 +452,19 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -300,13 +472,19 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::NoAnswer,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -314,19 +492,91 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
+                    part_1_status: PartStatus::NoAnswer,
+                    part_2_status: PartStatus::NoAnswer,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod from_records {
+        use crate::{
+            day,
+            template::records::{PartRecord, PartStatus, PARSE},
+            template::timings::Timing,
+        };
+
+        #[test]
+        fn collects_solved_parts() {
+            let records = [
+                PartRecord {
+                    day: day!(1),
+                    part: 1,
+                    answer: Some("42".into()),
+                    error: None,
+                    nanos: 74.13,
+                    samples: 100,
+                    status: PartStatus::Solved,
+                    stats: None,
+                },
+                PartRecord {
+                    day: day!(1),
+                    part: 2,
+                    answer: None,
+                    error: Some("bad input".into()),
+                    nanos: 12.0,
+                    samples: 1,
+                    status: PartStatus::Error,
+                    stats: None,
+                },
+            ];
+
+            let timing = Timing::from_records(day!(1), &records);
+            assert_eq!(timing.part_1, Some("74.0ns".into()));
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.part_2_status, PartStatus::Error);
+            assert_eq!(timing.total_nanos, 74.13);
+        }
+
+        #[test]
+        fn collects_parse_phase() {
+            let records = [PartRecord {
+                day: day!(1),
+                part: PARSE,
+                answer: None,
+                error: None,
+                nanos: 12.0,
+                samples: 1,
+                status: PartStatus::Solved,
+                stats: None,
+            }];
+
+            let timing = Timing::from_records(day!(1), &records);
+            assert_eq!(timing.parse, Some("12.0ns".into()));
+            assert_eq!(timing.total_nanos, 12.0);
+        }
+
+        #[test]
+        fn handles_missing_parts() {
+            let timing = Timing::from_records(day!(1), &[]);
+            assert_eq!(timing.part_1_status, PartStatus::NotImplemented);
+            assert_eq!(timing.part_2_status, PartStatus::NotImplemented);
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::records::PartStatus,
             template::timings::{Timing, Timings},
         };
 
@@ -338,8 +588,14 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
+                    part_1_status: PartStatus::NoAnswer,
+                    part_2_status: PartStatus::NoAnswer,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -358,8 +614,14 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
+                    part_1_status: PartStatus::NoAnswer,
+                    part_2_status: PartStatus::NoAnswer,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
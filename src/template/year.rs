@@ -0,0 +1,209 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+use crate::template::config::{self, ConfigError};
+#[cfg(feature = "today")]
+use crate::template::day::SERVER_UTC_OFFSET;
+use crate::template::Day;
+
+/// The first year of advent of code.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid year of advent of code (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year of advent of code,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The directory that holds the data of this year, e.g. `data/2023`.
+    /// Loads the configuration that sets the data directory if it is not loaded yet, which fails if it is invalid.
+    pub fn data_dir(self) -> Result<String, ConfigError> {
+        config::init().map(|config| format!("{}/{self}", config.data_dir))
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expecting a year of advent of code, {FIRST_YEAR} or later"
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a single puzzle by its year and day.
+/// Solutions live in `src/bin/<year>-<day>.rs`, their data in `data/<year>/`.
+///
+/// ```
+/// # use advent_of_code::template::{Day, Puzzle, Year};
+/// let puzzle = Puzzle::new(Year::new(2023).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.bin_name(), "2023-08");
+/// assert_eq!(puzzle.input_path().unwrap(), "data/2023/inputs/08.txt");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Parses the name of a solution binary, e.g. `2023-08`.
+    pub fn from_bin_name(name: &str) -> Option<Self> {
+        let (year, day) = name.split_once('-')?;
+        Some(Self::new(year.parse().ok()?, day.parse().ok()?))
+    }
+
+    /// The name of the binary that solves this puzzle, e.g. `2023-08`.
+    pub fn bin_name(self) -> String {
+        format!("{}-{}", self.year, self.day)
+    }
+
+    pub fn bin_path(self) -> String {
+        format!("src/bin/{}.rs", self.bin_name())
+    }
+
+    pub fn input_path(self) -> Result<String, ConfigError> {
+        Ok(format!("{}/inputs/{}.txt", self.year.data_dir()?, self.day))
+    }
+
+    /// The path of a file in the inputs directory of this puzzle's year, e.g. `08/alice.txt`.
+    pub fn inputs_path(self, file: &str) -> Result<String, ConfigError> {
+        Ok(format!("{}/inputs/{file}", self.year.data_dir()?))
+    }
+
+    pub fn puzzle_path(self) -> Result<String, ConfigError> {
+        Ok(format!("{}/puzzles/{}.md", self.year.data_dir()?, self.day))
+    }
+
+    /// The path of a file in the examples directory of this puzzle's year, e.g. `01-2.txt`.
+    pub fn example_path(self, file: &str) -> Result<String, ConfigError> {
+        Ok(format!("{}/examples/{file}", self.year.data_dir()?))
+    }
+
+    // Not part of the public API, returns the puzzle of the running solution binary.
+    #[doc(hidden)]
+    pub fn __for_bin(bin_name: Option<&str>, day: Day) -> Self {
+        match bin_name.and_then(Self::from_bin_name) {
+            Some(puzzle) if puzzle.day == day => puzzle,
+            _ => panic!(
+                "expecting the solution for day {day} to be a binary named `<year>-{day}`, got {bin_name:?}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "today")]
+impl Puzzle {
+    /// Returns the puzzle released today if it's between the 1st and the 25th of december, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let year = u16::try_from(Utc::now().with_timezone(&offset).year()).ok()?;
+        Some(Self::new(Year::new(year)?, Day::today()?))
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a year of advent of code"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Puzzle, Year};
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), year!(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn parses_bin_names() {
+        let puzzle = Puzzle::new(year!(2022), day!(5));
+        assert_eq!(Puzzle::from_bin_name(&puzzle.bin_name()), Some(puzzle));
+        assert_eq!(Puzzle::from_bin_name("05"), None);
+        assert_eq!(Puzzle::from_bin_name("2022-26"), None);
+    }
+}